@@ -0,0 +1,149 @@
+use crate::disjoint::DisjointSets;
+use std::collections::HashMap;
+
+/// Groups of points joined by chains of links, where two points are linked when they're within
+/// some radius of each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clusters<const N: usize> {
+    /// Each point's cluster, numbered from 0 in order of the cluster's first point.
+    pub labels: Vec<usize>,
+    /// How many points are in each cluster.
+    pub sizes: Vec<usize>,
+    /// The least and greatest coordinates along each axis of each cluster's points.
+    pub bounds: Vec<([i64; N], [i64; N])>,
+}
+
+impl<const N: usize> Clusters<N> {
+    /// How many clusters there are.
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+}
+
+pub fn manhattan<const N: usize>(a: &[i64; N], b: &[i64; N]) -> i64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum()
+}
+
+pub fn chebyshev<const N: usize>(a: &[i64; N], b: &[i64; N]) -> i64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).abs())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Clusters points that are within `radius` of each other by `distance`, directly or through
+/// other points. Linked points must be within `radius` along every axis too, which holds for
+/// `manhattan`, `chebyshev` and any other norm. That lets the points be bucketed into cells of
+/// side `radius`, so only the 3^N cells around each one need comparing.
+pub fn cluster<const N: usize>(
+    points: &[[i64; N]],
+    radius: i64,
+    distance: impl Fn(&[i64; N], &[i64; N]) -> i64,
+) -> Clusters<N> {
+    let side = radius.max(1);
+    let cell = |p: &[i64; N]| {
+        let mut cell = *p;
+        cell.iter_mut().for_each(|c| *c = c.div_euclid(side));
+        cell
+    };
+    let mut cells: HashMap<[i64; N], Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        cells.entry(cell(p)).or_default().push(i);
+    }
+    let offsets: Vec<[i64; N]> = (0..3usize.pow(N as u32))
+        .map(|mut n| {
+            let mut offset = [0; N];
+            for o in offset.iter_mut() {
+                *o = (n % 3) as i64 - 1;
+                n /= 3;
+            }
+            offset
+        })
+        .collect();
+
+    let mut sets = DisjointSets::new(points.len());
+    for (i, p) in points.iter().enumerate() {
+        let home = cell(p);
+        for offset in &offsets {
+            let mut neighbour = home;
+            neighbour.iter_mut().zip(offset).for_each(|(c, o)| *c += o);
+            for &j in cells.get(&neighbour).into_iter().flatten() {
+                if j > i && distance(p, &points[j]) <= radius {
+                    sets.union(i, j);
+                }
+            }
+        }
+    }
+
+    let labels = sets.labels();
+    let mut sizes = vec![0; sets.count()];
+    let mut bounds = vec![([i64::MAX; N], [i64::MIN; N]); sets.count()];
+    for (p, &label) in points.iter().zip(&labels) {
+        sizes[label] += 1;
+        let (low, high) = &mut bounds[label];
+        for axis in 0..N {
+            low[axis] = low[axis].min(p[axis]);
+            high[axis] = high[axis].max(p[axis]);
+        }
+    }
+    Clusters {
+        labels,
+        sizes,
+        bounds,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster() {
+        let points = [[0, 0], [2, 1], [4, 1], [10, 10], [-1, 0], [11, 12]];
+        let clusters = cluster(&points, 3, manhattan);
+        assert_eq!(vec![0, 0, 0, 1, 0, 1], clusters.labels);
+        assert_eq!(vec![4, 2], clusters.sizes);
+        assert_eq!(
+            vec![([-1, 0], [4, 1]), ([10, 10], [11, 12])],
+            clusters.bounds
+        );
+
+        let clusters = cluster(&points, 1, chebyshev);
+        assert_eq!(vec![0, 1, 2, 3, 0, 4], clusters.labels);
+        assert_eq!(5, clusters.len());
+
+        let clusters = cluster(&[[5], [5], [6]], 0, manhattan);
+        assert_eq!(vec![0, 0, 1], clusters.labels);
+        assert!(cluster::<3>(&[], 1, manhattan).is_empty());
+    }
+
+    #[test]
+    fn test_cluster_matches_every_pair() {
+        let mut state = 7u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % 41) as i64 - 20
+        };
+        let points: Vec<[i64; 3]> = (0..500).map(|_| [next(), next(), next()]).collect();
+        for &radius in &[1, 2, 5] {
+            let mut sets = DisjointSets::new(points.len());
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    if manhattan(&points[i], &points[j]) <= radius {
+                        sets.union(i, j);
+                    }
+                }
+            }
+            let clusters = cluster(&points, radius, manhattan);
+            assert_eq!(sets.labels(), clusters.labels);
+            assert_eq!(points.len(), clusters.sizes.iter().sum::<usize>());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/// A disjoint-set forest over `0..len`, with union by size and path halving.
+#[derive(Debug, Clone)]
+pub struct DisjointSets {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSets {
+    /// Puts each of `0..len` in a set of its own.
+    pub fn new(len: usize) -> Self {
+        DisjointSets {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// The representative of the set holding `x`.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets holding `a` and `b`, returning whether they were apart.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    /// How many sets there are.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Numbers the sets from 0, in order of their smallest member, and returns each element's.
+    pub fn labels(&mut self) -> Vec<usize> {
+        let mut numbers = vec![usize::MAX; self.parent.len()];
+        let mut next = 0;
+        (0..self.parent.len())
+            .map(|x| {
+                let root = self.find(x);
+                if numbers[root] == usize::MAX {
+                    numbers[root] = next;
+                    next += 1;
+                }
+                numbers[root]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let mut sets = DisjointSets::new(6);
+        assert!(sets.union(4, 1));
+        assert!(sets.union(1, 5));
+        assert!(!sets.union(5, 4));
+        assert!(sets.union(0, 2));
+        assert_eq!(3, sets.count());
+        assert_eq!(sets.find(4), sets.find(5));
+        assert_ne!(sets.find(0), sets.find(1));
+        assert_eq!(vec![0, 1, 0, 2, 1, 1], sets.labels());
+    }
+}
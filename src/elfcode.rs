@@ -0,0 +1,247 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::error::Error;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Multiply,
+    And,
+    Or,
+    Assign,
+    Greater,
+    Equals,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Parameter {
+    Register(usize),
+    Value(usize),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub a: Parameter,
+    pub b: Parameter,
+    pub c: Parameter,
+}
+
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 16] = [
+        Opcode::Addr,
+        Opcode::Addi,
+        Opcode::Mulr,
+        Opcode::Muli,
+        Opcode::Banr,
+        Opcode::Bani,
+        Opcode::Borr,
+        Opcode::Bori,
+        Opcode::Setr,
+        Opcode::Seti,
+        Opcode::Gtir,
+        Opcode::Gtri,
+        Opcode::Gtrr,
+        Opcode::Eqir,
+        Opcode::Eqri,
+        Opcode::Eqrr,
+    ];
+
+    pub fn instruction(self, a: usize, b: usize, c: usize) -> Instruction {
+        use self::{Opcode::*, Operation::*, Parameter::*};
+        macro_rules! instruction {
+            ($op:ident, $a:ident, $b:ident) => {
+                Instruction {
+                    operation: $op,
+                    a: $a(a),
+                    b: $b(b),
+                    c: Register(c),
+                }
+            };
+        }
+        match self {
+            Addr => instruction!(Add, Register, Register),
+            Addi => instruction!(Add, Register, Value),
+            Mulr => instruction!(Multiply, Register, Register),
+            Muli => instruction!(Multiply, Register, Value),
+            Banr => instruction!(And, Register, Register),
+            Bani => instruction!(And, Register, Value),
+            Borr => instruction!(Or, Register, Register),
+            Bori => instruction!(Or, Register, Value),
+            Setr => instruction!(Assign, Register, Register),
+            Seti => instruction!(Assign, Value, Value),
+            Gtir => instruction!(Greater, Value, Register),
+            Gtri => instruction!(Greater, Register, Value),
+            Gtrr => instruction!(Greater, Register, Register),
+            Eqir => instruction!(Equals, Value, Register),
+            Eqri => instruction!(Equals, Register, Value),
+            Eqrr => instruction!(Equals, Register, Register),
+        }
+    }
+}
+
+impl Instruction {
+    pub fn execute(self, registers: &mut [usize]) {
+        let (a, b) = (self.a.read(registers), self.b.read(registers));
+        use self::Operation::*;
+        let out_value = match self.operation {
+            Add => a + b,
+            Multiply => a * b,
+            And => a & b,
+            Or => a | b,
+            Assign => a,
+            Greater => {
+                if a > b {
+                    1
+                } else {
+                    0
+                }
+            }
+            Equals => {
+                if a == b {
+                    1
+                } else {
+                    0
+                }
+            }
+        };
+        self.c.write(registers, out_value);
+    }
+}
+
+impl Parameter {
+    pub fn read(self, registers: &[usize]) -> usize {
+        match self {
+            Parameter::Register(reg) => registers[reg],
+            Parameter::Value(val) => val,
+        }
+    }
+
+    pub fn write(self, registers: &mut [usize], val: usize) {
+        match self {
+            Parameter::Register(reg) => registers[reg] = val,
+            Parameter::Value(_) => unreachable!(),
+        }
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Opcode::*;
+        Ok(match s {
+            "addr" => Addr,
+            "addi" => Addi,
+            "mulr" => Mulr,
+            "muli" => Muli,
+            "banr" => Banr,
+            "bani" => Bani,
+            "borr" => Borr,
+            "bori" => Bori,
+            "setr" => Setr,
+            "seti" => Seti,
+            "gtir" => Gtir,
+            "gtri" => Gtri,
+            "gtrr" => Gtrr,
+            "eqir" => Eqir,
+            "eqri" => Eqri,
+            "eqrr" => Eqrr,
+            inst => return Err(format!("unknown instruction {}", inst).into()),
+        })
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^\s*(\w+)\s+(\d+)\s+(\d+)\s+(\d+)\s*$").unwrap();
+        }
+        let caps = RE
+            .captures(s)
+            .ok_or_else(|| Box::<dyn Error>::from("invalid instruction"))?;
+        let opcode: Opcode = caps[1].parse()?;
+        Ok(opcode.instruction(caps[2].parse()?, caps[3].parse()?, caps[4].parse()?))
+    }
+}
+
+/// Parses a program with an `#ip N` header, returning the instruction pointer register and the
+/// instructions.
+pub fn parse_program(input: &str) -> Result<(usize, Vec<Instruction>), Box<dyn Error>> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^#ip (\d+)$").unwrap();
+    }
+    let mut lines = input.trim().lines();
+    let header = lines.next().ok_or("empty program")?;
+    let ip_reg = RE.captures(header).ok_or("missing #ip header")?[1].parse()?;
+    let program = lines
+        .map(Instruction::from_str)
+        .collect::<Result<Vec<Instruction>, _>>()?;
+    Ok((ip_reg, program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_program() {
+        use self::Operation::*;
+        use self::Parameter::*;
+        let (ip_reg, program) = parse_program("#ip 3\nseti 5 0 1\ngtir 4 2 0").unwrap();
+        assert_eq!(3, ip_reg);
+        assert_eq!(
+            vec![
+                Instruction {
+                    operation: Assign,
+                    a: Value(5),
+                    b: Value(0),
+                    c: Register(1)
+                },
+                Instruction {
+                    operation: Greater,
+                    a: Value(4),
+                    b: Register(2),
+                    c: Register(0)
+                }
+            ],
+            program
+        );
+        assert!(parse_program("seti 5 0 1").is_err());
+        assert!(parse_program("#ip 0\nfoo 1 2 3").is_err());
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut registers = [3, 2, 1, 1];
+        Opcode::Mulr.instruction(2, 1, 2).execute(&mut registers);
+        assert_eq!([3, 2, 2, 1], registers);
+        Opcode::Eqri.instruction(0, 3, 3).execute(&mut registers);
+        assert_eq!([3, 2, 2, 1], registers);
+        Opcode::Gtrr.instruction(1, 0, 3).execute(&mut registers);
+        assert_eq!([3, 2, 2, 0], registers);
+    }
+}
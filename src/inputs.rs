@@ -0,0 +1,124 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A puzzle input found under `inputs/dayNN/`, along with any known answers for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub path: PathBuf,
+    pub contents: String,
+    pub answers: Answers,
+}
+
+/// Expected answers read from an `.answer` file: one line per part, in order. An empty line
+/// leaves that part unchecked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers(Vec<Option<String>>);
+
+impl Answers {
+    pub fn parse(input: &str) -> Answers {
+        Answers(
+            input
+                .lines()
+                .map(str::trim)
+                .map(|line| {
+                    if line.is_empty() {
+                        None
+                    } else {
+                        Some(line.to_owned())
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn part(&self, part: u32) -> Option<&str> {
+        self.0
+            .get(part.checked_sub(1)? as usize)
+            .and_then(|answer| answer.as_ref().map(String::as_str))
+    }
+}
+
+impl Input {
+    /// The file stem of the input, e.g. `alice` for `inputs/day12/alice.txt`.
+    pub fn name(&self) -> &str {
+        self.path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("")
+    }
+}
+
+pub fn day_dir(root: &Path, day: u32) -> PathBuf {
+    root.join(format!("day{:02}", day))
+}
+
+/// Finds every `*.txt` input for `day` under `root`, sorted by file name. A missing day directory
+/// is not an error; it simply has no inputs.
+pub fn discover(root: &Path, day: u32) -> io::Result<Vec<Input>> {
+    let dir = day_dir(root, day);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let contents = fs::read_to_string(&path)?;
+            let answer_path = path.with_extension("answer");
+            let answers = if answer_path.is_file() {
+                Answers::parse(&fs::read_to_string(&answer_path)?)
+            } else {
+                Answers::default()
+            };
+            Ok(Input {
+                path,
+                contents,
+                answers,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("325\n\n  42  \n");
+        assert_eq!(Some("325"), answers.part(1));
+        assert_eq!(None, answers.part(2));
+        assert_eq!(Some("42"), answers.part(3));
+        assert_eq!(None, answers.part(4));
+        assert_eq!(None, answers.part(0));
+    }
+
+    #[test]
+    fn test_discover() {
+        let root = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        let dir = day_dir(&root, 7);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bob.txt"), "b").unwrap();
+        fs::write(dir.join("alice.txt"), "a").unwrap();
+        fs::write(dir.join("alice.answer"), "1\n2\n").unwrap();
+        fs::write(dir.join("notes.md"), "ignored").unwrap();
+
+        let inputs = discover(&root, 7).unwrap();
+        let missing = discover(&root, 8).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            vec!["alice", "bob"],
+            inputs.iter().map(Input::name).collect::<Vec<_>>()
+        );
+        assert_eq!("a", inputs[0].contents);
+        assert_eq!(Some("2"), inputs[0].answers.part(2));
+        assert_eq!(Answers::default(), inputs[1].answers);
+        assert!(missing.is_empty());
+    }
+}
@@ -2,32 +2,17 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
-mod coordinate;
+pub mod cluster;
+pub mod coordinate;
+pub mod disjoint;
+mod elfcode;
+pub mod inputs;
+mod ocr;
+pub mod registry;
+pub mod report;
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+pub mod y2018;
 
+// cargo-aoc can only drive a single year, so it keeps pointing at 2018. Other years are reachable
+// through `registry::years`.
 aoc_lib! { year = 2018 }
@@ -0,0 +1,113 @@
+use aoc::inputs;
+use aoc::report;
+use aoc::registry::{self, Verdict};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+const USAGE: &str = "usage: aoc [--year YEAR] [--inputs DIR] [--json] [DAY [PART]]";
+
+struct Options {
+    year: Option<u32>,
+    root: PathBuf,
+    json: bool,
+    day: Option<u32>,
+    part: Option<u32>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        year: None,
+        root: PathBuf::from("inputs"),
+        json: false,
+        day: None,
+        part: None,
+    };
+    let mut args = env::args().skip(1);
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => {
+                let year = args.next().ok_or("--year needs a year")?;
+                options.year = Some(year.parse().map_err(|_| format!("invalid year {:?}", year))?);
+            }
+            "--inputs" => options.root = args.next().ok_or("--inputs needs a directory")?.into(),
+            "--json" => options.json = true,
+            "-h" | "--help" => return Err(USAGE.to_owned()),
+            _ => positional.push(
+                arg.parse::<u32>()
+                    .map_err(|_| format!("unexpected argument {:?}\n{}", arg, USAGE))?,
+            ),
+        }
+    }
+    match positional[..] {
+        [] => {}
+        [day] => options.day = Some(day),
+        [day, part] => {
+            options.day = Some(day);
+            options.part = Some(part);
+        }
+        _ => return Err(USAGE.to_owned()),
+    }
+    Ok(options)
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    });
+
+    let year = match options.year {
+        Some(year) => registry::year(year).unwrap_or_else(|| {
+            eprintln!("no solutions for {}", year);
+            process::exit(2);
+        }),
+        None => registry::years().last().unwrap(),
+    };
+    let root = options.root.join(year.year.to_string());
+
+    let mut failures = 0;
+    let solutions = (year.solutions)()
+        .iter()
+        .filter(|s| options.day.is_none_or(|day| s.day == day))
+        .filter(|s| options.part.is_none_or(|part| s.part == part));
+    for solution in solutions {
+        let day_inputs = inputs::discover(&root, solution.day).unwrap_or_else(|e| {
+            eprintln!("failed to read inputs for day {}: {}", solution.day, e);
+            process::exit(2);
+        });
+        for input in day_inputs {
+            let result = solution.run(&input.contents);
+            let expected = input.answers.part(solution.part);
+            let verdict = result.as_ref().ok().map(|run| run.check(expected));
+            if !matches!(verdict, Some(Verdict::Correct) | Some(Verdict::Unchecked)) {
+                failures += 1;
+            }
+            if options.json {
+                println!("{}", report::json(year.year, solution, &input, &result));
+                continue;
+            }
+            let label = format!("{} [{}]", solution.label(), input.name());
+            match result {
+                Ok(run) => {
+                    let verdict = match run.check(expected) {
+                        Verdict::Correct => "ok".to_owned(),
+                        Verdict::Unchecked => "unchecked".to_owned(),
+                        Verdict::Wrong { expected } => format!("WRONG, expected {}", expected),
+                    };
+                    println!(
+                        "{}: {} ({})\n\tgenerator: {:?},\n\trunner: {:?}",
+                        label, run.answer, verdict, run.generator, run.solver
+                    );
+                }
+                Err(e) => println!("{}: FAILED while {}", label, e),
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{} run(s) failed", failures);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,179 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+/// The block letters drawn by the puzzles, as a list of letters and a banner spelling them out
+/// with blank columns in between. Letters that have never shown up in a puzzle aren't listed.
+static FONTS: &[(&str, &str)] = &[
+    (
+        "ABCEFGHJKLNPRXZ",
+        "
+..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######
+.#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.
+#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..
+######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...
+#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....
+#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....
+#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....
+#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######",
+    ),
+    // The shorter font from the 2018 day 10 example.
+    (
+        "HI",
+        "
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###",
+    ),
+];
+
+lazy_static! {
+    static ref GLYPHS: HashMap<Vec<Vec<bool>>, char> = FONTS
+        .iter()
+        .flat_map(|(letters, banner)| {
+            let pixels = banner
+                .trim()
+                .lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect::<Vec<Vec<bool>>>();
+            let glyphs = split_glyphs(&pixels)
+                .into_iter()
+                .map(|columns| glyph(&pixels, columns))
+                .collect::<Vec<_>>();
+            assert_eq!(letters.len(), glyphs.len(), "malformed font {}", letters);
+            glyphs.into_iter().zip(letters.chars()).collect::<Vec<_>>()
+        })
+        .collect();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    UnsupportedHeight(usize),
+    UnknownGlyph { index: usize, columns: Range<usize> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "no known font is {} rows tall", height)
+            }
+            OcrError::UnknownGlyph { index, columns } => write!(
+                f,
+                "unrecognized glyph #{} in columns {}..{}",
+                index + 1,
+                columns.start,
+                columns.end
+            ),
+        }
+    }
+}
+
+/// Reads the block letters in `pixels`, given as rows of lit (`true`) and dark cells. The
+/// letters must fill the rows exactly, with at least one dark column between them.
+pub fn recognize(pixels: &[Vec<bool>]) -> Result<String, OcrError> {
+    if !GLYPHS.keys().any(|glyph| glyph.len() == pixels.len()) {
+        return Err(OcrError::UnsupportedHeight(pixels.len()));
+    }
+    split_glyphs(pixels)
+        .into_iter()
+        .enumerate()
+        .map(|(index, columns)| {
+            GLYPHS
+                .get(&glyph(pixels, columns.clone()))
+                .cloned()
+                .ok_or(OcrError::UnknownGlyph { index, columns })
+        })
+        .collect()
+}
+
+/// Finds the column ranges of each glyph, splitting on columns with nothing lit.
+fn split_glyphs(pixels: &[Vec<bool>]) -> Vec<Range<usize>> {
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |col: usize| pixels.iter().any(|row| row.get(col) == Some(&true));
+    let mut glyphs = Vec::new();
+    let mut start = None;
+    for col in 0..width {
+        match (start, lit(col)) {
+            (None, true) => start = Some(col),
+            (Some(s), false) => {
+                glyphs.push(s..col);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        glyphs.push(s..width);
+    }
+    glyphs
+}
+
+fn glyph(pixels: &[Vec<bool>], columns: Range<usize>) -> Vec<Vec<bool>> {
+    pixels
+        .iter()
+        .map(|row| {
+            columns
+                .clone()
+                .map(|col| row.get(col) == Some(&true))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(art: &str) -> Vec<Vec<bool>> {
+        art.trim()
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_recognize() {
+        assert_eq!(Ok("ABCEFGHJKLNPRXZ".to_owned()), recognize(&pixels(FONTS[0].1)));
+        assert_eq!(Ok("HI".to_owned()), recognize(&pixels(FONTS[1].1)));
+        let narrow = pixels(
+            "
+#....#.#####.
+#....#.#....#
+#....#.#....#
+#....#.#....#
+######.#####.
+#....#.#..#..
+#....#.#...#.
+#....#.#...#.
+#....#.#....#
+#....#.#....#",
+        );
+        assert_eq!(Ok("HR".to_owned()), recognize(&narrow));
+    }
+
+    #[test]
+    fn test_unrecognized() {
+        assert_eq!(
+            Err(OcrError::UnsupportedHeight(3)),
+            recognize(&pixels("###\n#.#\n###"))
+        );
+        let mut art = pixels(FONTS[1].1);
+        art[0][8] = false;
+        assert_eq!(
+            Err(OcrError::UnknownGlyph {
+                index: 1,
+                columns: 7..10
+            }),
+            recognize(&art)
+        );
+    }
+}
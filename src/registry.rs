@@ -0,0 +1,154 @@
+use aoc_runner::{ArcStr, Runner};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+pub type Build = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A single solution, runnable against any input. For 2018 these wrap the runners that `#[aoc]`
+/// generates.
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub name: Option<&'static str>,
+    build: Build,
+}
+
+/// The answer a solution produced for one input, along with how long each stage took.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub answer: String,
+    pub generator: Duration,
+    pub solver: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unchecked,
+}
+
+impl Solution {
+    pub const fn new(day: u32, part: u32, name: Option<&'static str>, build: Build) -> Self {
+        Solution {
+            day,
+            part,
+            name,
+            build,
+        }
+    }
+
+    /// Runs the generator and solver on `input`. Panics inside the solution are reported as
+    /// errors so that one bad input doesn't stop a whole sweep.
+    pub fn run(&self, input: &str) -> Result<Run, String> {
+        let input = ArcStr::from(input);
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let start_time = Instant::now();
+            let runner = (self.build)(input).map_err(|e| format!("generating: {}", e))?;
+            let inter_time = Instant::now();
+            let answer = runner
+                .try_run()
+                .map_err(|e| format!("running: {}", e))?
+                .to_string();
+            let final_time = Instant::now();
+            Ok(Run {
+                answer,
+                generator: inter_time - start_time,
+                solver: final_time - inter_time,
+            })
+        }))
+        .unwrap_or_else(|cause| Err(format!("panicked: {}", panic_message(&*cause))))
+    }
+
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(name) => format!("Day {} - Part {} - {}", self.day, self.part, name),
+            None => format!("Day {} - Part {}", self.day, self.part),
+        }
+    }
+}
+
+impl Run {
+    pub fn check(&self, expected: Option<&str>) -> Verdict {
+        match expected {
+            None => Verdict::Unchecked,
+            Some(expected) if expected == self.answer.trim() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+fn panic_message(cause: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(message) = cause.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = cause.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
+}
+
+macro_rules! solutions {
+    ($(($day:expr, $part:expr, $name:expr, $build:path)),* $(,)*) => {
+        {
+            const SOLUTIONS: &[$crate::registry::Solution] =
+                &[$($crate::registry::Solution::new($day, $part, $name, $build)),*];
+            SOLUTIONS
+        }
+    };
+}
+
+pub(crate) use solutions;
+
+/// A year of puzzles, e.g. `y2018`, and the solutions written for it.
+pub struct Year {
+    pub year: u32,
+    pub solutions: fn() -> &'static [Solution],
+}
+
+/// Every year with solutions in the crate, oldest first.
+pub fn years() -> &'static [Year] {
+    &[Year {
+        year: 2018,
+        solutions: crate::y2018::solutions,
+    }]
+}
+
+pub fn year(year: u32) -> Option<&'static Year> {
+    years().iter().find(|y| y.year == year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_and_check() {
+        let solution = (year(2018).unwrap().solutions)()
+            .iter()
+            .find(|s| s.day == 1 && s.part == 1)
+            .unwrap();
+        let run = solution.run("+1\n-2\n+3\n+1\n").unwrap();
+        assert_eq!("3", run.answer);
+        assert_eq!(Verdict::Correct, run.check(Some("3")));
+        assert_eq!(
+            Verdict::Wrong {
+                expected: "4".to_owned()
+            },
+            run.check(Some("4"))
+        );
+        assert_eq!(Verdict::Unchecked, run.check(None));
+    }
+
+    #[test]
+    fn test_run_reports_panics() {
+        let solution = (year(2018).unwrap().solutions)()
+            .iter()
+            .find(|s| s.day == 8 && s.part == 1 && s.name.is_none())
+            .unwrap();
+        assert!(solution.run("not numbers").unwrap_err().starts_with("panicked"));
+    }
+}
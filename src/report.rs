@@ -0,0 +1,117 @@
+use crate::inputs::Input;
+use crate::registry::{Run, Solution, Verdict};
+use std::fmt::Write;
+
+/// Hashes an input's contents with 64-bit FNV-1a, so results can be matched up with the input
+/// they came from without shipping the input itself.
+pub fn input_hash(contents: &str) -> String {
+    let hash = contents.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Formats the result of running `solution` on `input` as a single-line JSON object. Answers are
+/// always strings, since some days produce text (or ASCII art) rather than numbers. Times are in
+/// nanoseconds.
+pub fn json(year: u32, solution: &Solution, input: &Input, result: &Result<Run, String>) -> String {
+    let mut out = format!(
+        "{{\"year\":{},\"day\":{},\"part\":{},\"variant\":{},\"input\":{},\"input_hash\":{}",
+        year,
+        solution.day,
+        solution.part,
+        solution.name.map_or("null".to_owned(), quote),
+        quote(input.name()),
+        quote(&input_hash(&input.contents)),
+    );
+    let expected = input.answers.part(solution.part);
+    match result {
+        Ok(run) => {
+            let status = match run.check(expected) {
+                Verdict::Correct => "correct",
+                Verdict::Wrong { .. } => "wrong",
+                Verdict::Unchecked => "unchecked",
+            };
+            write!(
+                out,
+                ",\"status\":\"{}\",\"answer\":{},\"generator_ns\":{},\"solver_ns\":{}",
+                status,
+                quote(&run.answer),
+                run.generator.as_nanos(),
+                run.solver.as_nanos(),
+            )
+            .unwrap();
+        }
+        Err(e) => write!(out, ",\"status\":\"failed\",\"error\":{}", quote(e)).unwrap(),
+    }
+    if let Some(expected) = expected {
+        write!(out, ",\"expected\":{}", quote(expected)).unwrap();
+    }
+    out.push('}');
+    out
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::Answers;
+    use crate::registry;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!("cbf29ce484222325", input_hash(""));
+        assert_eq!("af63dc4c8601ec8c", input_hash("a"));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(r#""a\"b\\c\n▓\u0001""#, quote("a\"b\\c\n▓\u{1}"));
+    }
+
+    #[test]
+    fn test_json() {
+        let solution = &(registry::year(2018).unwrap().solutions)()[0];
+        let input = Input {
+            path: PathBuf::from("inputs/2018/day01/alice.txt"),
+            contents: "a".to_owned(),
+            answers: Answers::parse("3"),
+        };
+        let run = Run {
+            answer: "3".to_owned(),
+            generator: Duration::from_nanos(12),
+            solver: Duration::from_micros(1),
+        };
+        assert_eq!(
+            "{\"year\":2018,\"day\":1,\"part\":1,\"variant\":null,\"input\":\"alice\",\
+             \"input_hash\":\"af63dc4c8601ec8c\",\"status\":\"correct\",\"answer\":\"3\",\
+             \"generator_ns\":12,\"solver_ns\":1000,\"expected\":\"3\"}",
+            json(2018, solution, &input, &Ok(run))
+        );
+        assert_eq!(
+            "{\"year\":2018,\"day\":1,\"part\":1,\"variant\":null,\"input\":\"alice\",\
+             \"input_hash\":\"af63dc4c8601ec8c\",\"status\":\"failed\",\"error\":\"panicked: x\",\
+             \"expected\":\"3\"}",
+            json(2018, solution, &input, &Err("panicked: x".to_owned()))
+        );
+    }
+}
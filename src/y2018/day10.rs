@@ -0,0 +1,269 @@
+use crate::ocr;
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub dx: i32,
+    pub dy: i32,
+}
+
+#[aoc_generator(day10)]
+pub fn parse(input: &str) -> Vec<Point> {
+    lazy_static::lazy_static! {
+        static ref RE: Regex = Regex::new(r"^position=<\s*(-?\d+),\s*(-?\d+)> velocity=<\s*(-?\d+),\s*(-?\d+)>$").unwrap();
+    }
+    input
+        .lines()
+        .filter_map(|line| {
+            let caps = RE.captures(line)?;
+            Some(Point {
+                x: caps[1].parse().unwrap(),
+                y: caps[2].parse().unwrap(),
+                dx: caps[3].parse().unwrap(),
+                dy: caps[4].parse().unwrap(),
+            })
+        })
+        .collect()
+}
+
+/// When the points come together, and how sure that is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Convergence {
+    /// The second, from the start, when the points' bounding box is smallest.
+    pub time: i32,
+    /// How much smaller the bounding box is at `time` than at the smaller of the boxes a second
+    /// either side, as a fraction of that: `1 - area / neighbour`. It's 0 when the box is just
+    /// as big a second away, so nothing converged and `time` is only a guess, and approaches 1
+    /// the more sharply the points snap together.
+    pub confidence: f64,
+}
+
+fn find_minimum_variance_time(points: &[Point]) -> i32 {
+    // At each time t, the spread of the points around their centroid is
+    // sum((x - mx + t * (dx - mdx))^2 + (y - my + t * (dy - mdy))^2), where mx etc. are the means.
+    // Unlike the distance to the origin, this doesn't care where in the sky the message shows up.
+    // It's minimized at t = -sum((x - mx) * (dx - mdx) + (y - my) * (dy - mdy)) /
+    // sum((dx - mdx)^2 + (dy - mdy)^2).
+    let n = points.len() as f64;
+    let mean = |f: fn(&Point) -> i32| points.iter().map(|p| f64::from(f(p))).sum::<f64>() / n;
+    let (mx, my, mdx, mdy) = (mean(|p| p.x), mean(|p| p.y), mean(|p| p.dx), mean(|p| p.dy));
+    let (numerator, denominator) = points.iter().fold((0.0, 0.0), |(num, den), p| {
+        let (x, y) = (f64::from(p.x) - mx, f64::from(p.y) - my);
+        let (dx, dy) = (f64::from(p.dx) - mdx, f64::from(p.dy) - mdy);
+        (num + x * dx + y * dy, den + dx * dx + dy * dy)
+    });
+    if denominator == 0.0 {
+        return 0;
+    }
+    (-numerator / denominator).round() as i32
+}
+
+fn bounding_area(points: &[Point], t: i32) -> i64 {
+    let xs = points.iter().map(|p| i64::from(p.x + p.dx * t));
+    let ys = points.iter().map(|p| i64::from(p.y + p.dy * t));
+    let width = xs.clone().max().unwrap() - xs.min().unwrap() + 1;
+    let height = ys.clone().max().unwrap() - ys.min().unwrap() + 1;
+    width * height
+}
+
+/// Finds when the points converge, by looking for the smallest bounding box around the time their
+/// spread is least.
+pub fn find_convergence(points: &[Point]) -> Convergence {
+    // The centroid estimate lands close to the message, but not necessarily on it. Refine it by
+    // ternary searching for the smallest bounding box nearby, then check the neighbours in case
+    // the search stopped on a plateau.
+    let estimate = find_minimum_variance_time(points);
+    let window = estimate.abs() / 4 + 16;
+    let (mut lo, mut hi) = (std::cmp::max(0, estimate - window), estimate + window);
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+        if bounding_area(points, m1) <= bounding_area(points, m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    let time = (lo - 1..=hi + 1)
+        .filter(|&t| t >= 0)
+        .min_by_key(|&t| (bounding_area(points, t), t))
+        .unwrap();
+    let area = bounding_area(points, time) as f64;
+    let neighbour = std::cmp::min(
+        bounding_area(points, time - 1),
+        bounding_area(points, time + 1),
+    ) as f64;
+    Convergence {
+        time,
+        confidence: 1.0 - area / neighbour,
+    }
+}
+
+fn change_time(points: &mut [Point], dt: i32) {
+    for point in points {
+        point.x += point.dx * dt;
+        point.y += point.dy * dt;
+    }
+}
+
+fn light_sky(points: &[Point]) -> Vec<Vec<bool>> {
+    let minx = points.iter().map(|p| p.x).min().unwrap();
+    let maxx = points.iter().map(|p| p.x).max().unwrap();
+    let miny = points.iter().map(|p| p.y).min().unwrap();
+    let maxy = points.iter().map(|p| p.y).max().unwrap();
+    let mut sky = vec![vec![false; (maxx - minx + 1) as usize]; (maxy - miny + 1) as usize];
+    for point in points {
+        sky[(point.y - miny) as usize][(point.x - minx) as usize] = true;
+    }
+    sky
+}
+
+fn plot_sky(sky: &[Vec<bool>]) -> String {
+    format!(
+        "\n\n{}\n",
+        sky.iter()
+            .map(|line| line
+                .iter()
+                .map(|&lit| if lit { '▓' } else { ' ' })
+                .collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    )
+}
+
+#[aoc(day10, part1)]
+fn solve_part1(points: &[Point]) -> String {
+    let mut points: Vec<Point> = points.iter().cloned().collect();
+    let dt = find_convergence(&points).time;
+    change_time(&mut points, dt);
+    let sky = light_sky(&points);
+    // Fall back to drawing the message if it isn't in a font we know.
+    ocr::recognize(&sky).unwrap_or_else(|e| format!("{}{}", e, plot_sky(&sky)))
+}
+
+#[aoc(day10, part2)]
+fn solve_part2(points: &[Point]) -> i32 {
+    find_convergence(points).time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+    #[test]
+    fn test_parse() {
+        let expected = &[
+            Point {
+                x: 9,
+                y: 1,
+                dx: 0,
+                dy: 2,
+            },
+            Point {
+                x: 7,
+                y: 0,
+                dx: -1,
+                dy: 0,
+            },
+        ];
+        assert_eq!(expected, &parse(INPUT)[..2]);
+    }
+
+    #[test]
+    fn test_find_variance_time() {
+        assert_eq!(3, find_minimum_variance_time(&parse(INPUT)));
+    }
+
+    #[test]
+    fn test_find_convergence() {
+        let convergence = find_convergence(&parse(INPUT));
+        assert_eq!(3, convergence.time);
+        assert!(convergence.confidence > 0.4);
+
+        // Far away from the origin, where the message converging isn't the same as the points
+        // getting closest to the origin.
+        let mut points = parse(INPUT);
+        for point in &mut points {
+            point.x += 5000 - point.dx * 1000;
+            point.y -= 3000 + point.dy * 1000;
+        }
+        assert_eq!(1003, find_convergence(&points).time);
+        assert_eq!(1003, solve_part2(&points));
+        assert_eq!("HI", solve_part1(&points));
+
+        // Points drifting together in formation never converge.
+        let mut points = parse(INPUT);
+        for point in &mut points {
+            point.dx = 1;
+            point.dy = -1;
+        }
+        assert_eq!(0.0, find_convergence(&points).confidence);
+    }
+
+    #[test]
+    fn test_change_time() {
+        let mut points = parse(INPUT);
+        change_time(&mut points, 3);
+        let expected = &[
+            Point {
+                x: 9,
+                y: 7,
+                dx: 0,
+                dy: 2,
+            },
+            Point {
+                x: 4,
+                y: 0,
+                dx: -1,
+                dy: 0,
+            },
+        ];
+        assert_eq!(expected, &points[..2]);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!("HI", solve_part1(&parse(INPUT)));
+    }
+
+    #[test]
+    fn test_part1_unrecognized() {
+        let points = &parse(INPUT)[..30];
+        assert!(solve_part1(points).starts_with("unrecognized glyph"));
+    }
+}
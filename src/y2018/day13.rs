@@ -0,0 +1,795 @@
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Track {
+    track: Vec<Vec<Path>>,
+    carts: BinaryHeap<Reverse<Cart>>,
+    cart_waiting_room: BinaryHeap<Reverse<Cart>>,
+    positions: HashMap<Position, Direction>,
+    crashes: HashSet<Position>,
+}
+
+#[derive(Clone, Debug)]
+struct Cart {
+    id: usize,
+    position: Position,
+    direction: Direction,
+    policy: Arc<dyn TurnPolicy>,
+    intersections: usize,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Path {
+    Empty,
+    Vertical,
+    Horizontal,
+    CurveRight,
+    CurveLeft,
+    Intersection,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Turn {
+    Left,
+    Straight,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// Decides which way carts go at intersections.
+pub trait TurnPolicy: Debug + Send + Sync {
+    /// The turn to take at the cart's `n`th intersection, counting from 0.
+    fn turn(&self, n: usize) -> Turn;
+}
+
+/// Goes through the turns in order, starting over at the end. The puzzle's carts use
+/// `Cycle::default()`: left, straight, right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(Vec<Turn>);
+
+/// Takes the same turn every time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Always(pub Turn);
+
+/// Picks turns at random, but the same ones every time for a given seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Random(pub u64);
+
+/// A problem with a track's rails, found before any cart moves.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TrackError {
+    /// A straight rail doesn't lead onto track at one of its ends.
+    DeadEnd(Position),
+    /// A curve doesn't join two neighbouring rails.
+    BrokenCurve(Position),
+    /// An intersection is missing a rail on one of its four sides.
+    BrokenIntersection(Position),
+    /// A cart isn't on a rail that runs the way it's facing.
+    CartOffRail(Position),
+}
+
+/// Something that happened during a tick. Carts are numbered in reading order, as parsed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Event {
+    Moved {
+        cart: usize,
+        from: Position,
+        to: Position,
+    },
+    /// A cart went through an intersection, before moving off it.
+    Turned {
+        cart: usize,
+        at: Position,
+        turn: Turn,
+    },
+    /// A cart ran into another, and both were removed.
+    Crashed {
+        at: Position,
+        cart: usize,
+        other: usize,
+    },
+}
+
+impl Track {
+    /// Makes cart number `cart` (in reading order, as parsed) use `policy` from now on.
+    pub fn set_policy(&mut self, cart: usize, policy: Arc<dyn TurnPolicy>) {
+        self.carts = self
+            .carts
+            .drain()
+            .map(|Reverse(mut c)| {
+                if c.id == cart {
+                    c.policy = policy.clone();
+                    c.intersections = 0;
+                }
+                Reverse(c)
+            })
+            .collect();
+    }
+
+    /// Makes every cart use `policy` from now on.
+    pub fn set_policies(&mut self, policy: Arc<dyn TurnPolicy>) {
+        let ids: Vec<_> = self.carts.iter().map(|Reverse(c)| c.id).collect();
+        for id in ids {
+            self.set_policy(id, policy.clone());
+        }
+    }
+
+    /// Moves every cart once, returning the first crash.
+    fn step(&mut self) -> Option<Position> {
+        self.tick().into_iter().find_map(|event| match event {
+            Event::Crashed { at, .. } => Some(at),
+            _ => None,
+        })
+    }
+
+    /// Moves every cart once, in reading order, and reports what happened.
+    pub fn tick(&mut self) -> Vec<Event> {
+        self.crashes.clear();
+        let mut events = vec![];
+        while let Some(Reverse(mut cart)) = self.carts.pop() {
+            let from = cart.position;
+            self.positions.remove(&from);
+            if let Some(turn) = cart.ride(self.track[from.y][from.x]) {
+                events.push(Event::Turned {
+                    cart: cart.id,
+                    at: from,
+                    turn,
+                });
+            }
+            events.push(Event::Moved {
+                cart: cart.id,
+                from,
+                to: cart.position,
+            });
+            if self.positions.contains_key(&cart.position) {
+                events.push(Event::Crashed {
+                    at: cart.position,
+                    cart: cart.id,
+                    other: self.cart_at(cart.position).unwrap().id,
+                });
+                self.crashes.insert(cart.position);
+                self.blow_up_at(cart.position);
+            } else {
+                self.positions.insert(cart.position, cart.direction);
+                self.cart_waiting_room.push(Reverse(cart));
+            }
+        }
+        std::mem::swap(&mut self.carts, &mut self.cart_waiting_room);
+        events
+    }
+
+    /// Checks that every rail joins up with its neighbours, so that no cart can ever derail.
+    pub fn validate(&self) -> Result<(), TrackError> {
+        use self::Direction::*;
+        let mut carts: Vec<_> = self.positions.iter().collect();
+        carts.sort_by_key(|(p, _)| (p.y, p.x));
+        for (&position, &direction) in carts {
+            // The rail under a cart is assumed to run its way, so it has to lead on both ways.
+            let behind = direction.handle_turn(Turn::Left).handle_turn(Turn::Left);
+            if !self.joins(position, direction) || !self.joins(position, behind) {
+                return Err(TrackError::CartOffRail(position));
+            }
+        }
+        for (y, row) in self.track.iter().enumerate() {
+            for (x, &path) in row.iter().enumerate() {
+                let position = Position { x, y };
+                let joined = |sides: &[Direction]| sides.iter().all(|&d| self.joins(position, d));
+                match path {
+                    Path::Empty => {}
+                    Path::Vertical | Path::Horizontal => {
+                        let ends = if path == Path::Vertical {
+                            [Up, Down]
+                        } else {
+                            [Left, Right]
+                        };
+                        if !joined(&ends) {
+                            return Err(TrackError::DeadEnd(position));
+                        }
+                    }
+                    Path::CurveRight if !joined(&[Down, Right]) && !joined(&[Up, Left]) => {
+                        return Err(TrackError::BrokenCurve(position));
+                    }
+                    Path::CurveLeft if !joined(&[Down, Left]) && !joined(&[Up, Right]) => {
+                        return Err(TrackError::BrokenCurve(position));
+                    }
+                    Path::Intersection if !joined(&[Up, Right, Down, Left]) => {
+                        return Err(TrackError::BrokenIntersection(position));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the cell next to `position` in `direction` has a rail leading back. A curve only
+    /// leads back if the side it pairs with that one leads on to a rail in turn.
+    fn joins(&self, position: Position, direction: Direction) -> bool {
+        match self.rail_towards(position, direction) {
+            Some((next, path @ Path::CurveRight)) | Some((next, path @ Path::CurveLeft)) => self
+                .rail_towards(next, direction.handle_curve(path))
+                .is_some(),
+            rail => rail.is_some(),
+        }
+    }
+
+    /// The cell next to `position` in `direction`, if it has a rail that could lead back. Curves
+    /// and intersections always could.
+    fn rail_towards(&self, position: Position, direction: Direction) -> Option<(Position, Path)> {
+        let (dx, dy) = direction.to_offsets();
+        let (x, y) = (position.x as i32 + dx, position.y as i32 + dy);
+        if x < 0 || y < 0 {
+            return None;
+        }
+        let next = Position {
+            x: x as usize,
+            y: y as usize,
+        };
+        let path = *self.track.get(next.y)?.get(next.x)?;
+        let leads_back = match path {
+            Path::Empty => false,
+            Path::Vertical => dx == 0,
+            Path::Horizontal => dy == 0,
+            _ => true,
+        };
+        if leads_back {
+            Some((next, path))
+        } else {
+            None
+        }
+    }
+
+    fn cart_at(&self, position: Position) -> Option<&Cart> {
+        self.carts
+            .iter()
+            .chain(self.cart_waiting_room.iter())
+            .map(|Reverse(c)| c)
+            .find(|c| c.position == position)
+    }
+
+    /// The direction a cart will leave `position` in. This is how the puzzle draws carts: already
+    /// turned on curves and intersections.
+    fn heading(&self, position: Position, direction: Direction) -> Direction {
+        match self.track[position.y][position.x] {
+            path @ Path::CurveRight | path @ Path::CurveLeft => direction.handle_curve(path),
+            Path::Intersection => match self.cart_at(position) {
+                Some(cart) => direction.handle_turn(cart.policy.turn(cart.intersections)),
+                None => direction,
+            },
+            _ => direction,
+        }
+    }
+
+    fn blow_up_at(&mut self, position: Position) {
+        self.carts = self
+            .carts
+            .drain()
+            .filter(|Reverse(c)| c.position != position)
+            .collect();
+        self.cart_waiting_room = self
+            .cart_waiting_room
+            .drain()
+            .filter(|Reverse(c)| c.position != position)
+            .collect();
+        self.positions.remove(&position);
+    }
+}
+
+impl Cart {
+    /// Moves the cart along `path`, returning the turn it took if it was at an intersection.
+    fn ride(&mut self, path: Path) -> Option<Turn> {
+        let mut x = self.position.x as i32;
+        let mut y = self.position.y as i32;
+        let mut turned = None;
+        use self::Path::*;
+        match path {
+            Vertical => y += self.direction.to_offsets().1,
+            Horizontal => x += self.direction.to_offsets().0,
+            CurveRight | CurveLeft => {
+                self.direction = self.direction.handle_curve(path);
+                x += self.direction.to_offsets().0;
+                y += self.direction.to_offsets().1;
+            }
+            Intersection => {
+                let turn = self.next_turn();
+                turned = Some(turn);
+                self.direction = self.direction.handle_turn(turn);
+                x += self.direction.to_offsets().0;
+                y += self.direction.to_offsets().1;
+            }
+            Empty => unreachable!(
+                "derailed at {} on a track that wasn't validated",
+                self.position
+            ),
+        }
+        self.position = Position {
+            x: x as usize,
+            y: y as usize,
+        };
+        turned
+    }
+
+    fn next_turn(&mut self) -> Turn {
+        let turn = self.policy.turn(self.intersections);
+        self.intersections += 1;
+        turn
+    }
+}
+
+impl Direction {
+    fn to_offsets(&self) -> (i32, i32) {
+        use self::Direction::*;
+        let dx = if self == &Up || self == &Down {
+            0
+        } else if self == &Left {
+            -1
+        } else {
+            1
+        };
+        let dy = if self == &Left || self == &Right {
+            0
+        } else if self == &Up {
+            -1
+        } else {
+            1
+        };
+        (dx, dy)
+    }
+
+    fn handle_curve(&self, path: Path) -> Direction {
+        use self::Direction::*;
+        use self::Path::*;
+        match path {
+            CurveRight => match self {
+                Up => Right,
+                Right => Up,
+                Left => Down,
+                Down => Left,
+            },
+            CurveLeft => match self {
+                Up => Left,
+                Left => Up,
+                Right => Down,
+                Down => Right,
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn handle_turn(&self, turn: Turn) -> Direction {
+        use self::Direction::*;
+        match turn {
+            Turn::Straight => *self,
+            Turn::Left => match self {
+                Up => Left,
+                Left => Down,
+                Down => Right,
+                Right => Up,
+            },
+            Turn::Right => match self {
+                Up => Right,
+                Right => Down,
+                Down => Left,
+                Left => Up,
+            },
+        }
+    }
+}
+
+impl Ord for Cart {
+    fn cmp(&self, other: &Cart) -> Ordering {
+        match self.position.y.cmp(&other.position.y) {
+            Ordering::Equal => self.position.x.cmp(&other.position.x),
+            x => x,
+        }
+    }
+}
+
+impl PartialEq for Cart {
+    fn eq(&self, other: &Cart) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cart {}
+
+impl PartialOrd for Cart {
+    fn partial_cmp(&self, other: &Cart) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Default for Cycle {
+    fn default() -> Self {
+        Cycle(vec![Turn::Left, Turn::Straight, Turn::Right])
+    }
+}
+
+impl Cycle {
+    /// A cycle through `turns`, which mustn't be empty.
+    pub fn new(turns: Vec<Turn>) -> Option<Cycle> {
+        if turns.is_empty() {
+            None
+        } else {
+            Some(Cycle(turns))
+        }
+    }
+}
+
+impl TurnPolicy for Cycle {
+    fn turn(&self, n: usize) -> Turn {
+        self.0[n % self.0.len()]
+    }
+}
+
+impl TurnPolicy for Always {
+    fn turn(&self, _: usize) -> Turn {
+        self.0
+    }
+}
+
+impl TurnPolicy for Random {
+    fn turn(&self, n: usize) -> Turn {
+        // SplitMix64, keyed on the seed and the intersection count.
+        let mut z = self
+            .0
+            .wrapping_add((n as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        [Turn::Left, Turn::Straight, Turn::Right][((z ^ (z >> 31)) % 3) as usize]
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Draws the track like the puzzle does, with carts over the rails and an `X` wherever carts
+/// crashed during the last tick.
+impl Display for Track {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (y, row) in self.track.iter().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, path) in row.iter().enumerate() {
+                let position = Position { x, y };
+                let c = if self.crashes.contains(&position) {
+                    'X'
+                } else if let Some(&direction) = self.positions.get(&position) {
+                    match self.heading(position, direction) {
+                        Direction::Up => '^',
+                        Direction::Right => '>',
+                        Direction::Down => 'v',
+                        Direction::Left => '<',
+                    }
+                } else {
+                    match path {
+                        Path::Empty => ' ',
+                        Path::Vertical => '|',
+                        Path::Horizontal => '-',
+                        Path::CurveRight => '/',
+                        Path::CurveLeft => '\\',
+                        Path::Intersection => '+',
+                    }
+                };
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for TrackError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TrackError::DeadEnd(p) => write!(f, "rail at {} leads nowhere", p),
+            TrackError::BrokenCurve(p) => write!(f, "curve at {} doesn't join two rails", p),
+            TrackError::BrokenIntersection(p) => {
+                write!(f, "intersection at {} doesn't have four rails", p)
+            }
+            TrackError::CartOffRail(p) => write!(f, "cart at {} isn't on a matching rail", p),
+        }
+    }
+}
+
+impl Error for TrackError {}
+
+#[aoc_generator(day13)]
+pub fn parse(input: &str) -> Result<Box<Track>, TrackError> {
+    use self::Direction::*;
+    use self::Path::*;
+    let policy: Arc<dyn TurnPolicy> = Arc::new(Cycle::default());
+    let mut carts = BinaryHeap::new();
+    let track: Vec<_> = input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, ch)| match ch {
+                    '|' => Vertical,
+                    '-' => Horizontal,
+                    '/' => CurveRight,
+                    '\\' => CurveLeft,
+                    '+' => Intersection,
+                    '^' | 'v' => {
+                        carts.push(Reverse(Cart {
+                            id: carts.len(),
+                            position: Position { x, y },
+                            direction: if ch == 'v' { Down } else { Up },
+                            policy: policy.clone(),
+                            intersections: 0,
+                        }));
+                        Vertical
+                    }
+                    '>' | '<' => {
+                        carts.push(Reverse(Cart {
+                            id: carts.len(),
+                            position: Position { x, y },
+                            direction: if ch == '<' { Left } else { Right },
+                            policy: policy.clone(),
+                            intersections: 0,
+                        }));
+                        Horizontal
+                    }
+                    _ => Empty,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let track = Track {
+        track,
+        cart_waiting_room: BinaryHeap::with_capacity(carts.len()),
+        positions: carts
+            .iter()
+            .map(|Reverse(c)| (c.position, c.direction))
+            .collect(),
+        carts,
+        crashes: HashSet::new(),
+    };
+    track.validate()?;
+    Ok(Box::new(track))
+}
+
+#[aoc(day13, part1)]
+fn solve_part1(track: &Track) -> Position {
+    let mut track = track.clone();
+    loop {
+        if let Some(crash) = track.step() {
+            break crash;
+        }
+    }
+}
+
+#[aoc(day13, part2)]
+fn solve_part2(track: &Track) -> Position {
+    let mut track = track.clone();
+    while track.carts.len() > 1 {
+        track.step();
+    }
+    track.carts.pop().unwrap().0.position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = r#"/->-\        
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/   "#;
+
+    #[test]
+    fn test_parse() {
+        let mut track = parse(EXAMPLE).unwrap();
+        let mut cart = || {
+            let Reverse(mut cart) = track.carts.pop().unwrap();
+            (cart.id, cart.position, cart.direction, cart.next_turn())
+        };
+        assert_eq!(
+            (0, Position { x: 2, y: 0 }, Direction::Right, Turn::Left),
+            cart()
+        );
+        assert_eq!(
+            (1, Position { x: 9, y: 3 }, Direction::Down, Turn::Left),
+            cart()
+        );
+        assert!(track.carts.is_empty());
+        assert_eq!(Path::CurveRight, track.track[0][0]);
+        assert_eq!(Path::CurveLeft, track.track[4][0]);
+        assert_eq!(Path::Intersection, track.track[2][4]);
+        assert_eq!(Path::Vertical, track.track[1][0]);
+        assert_eq!(Path::Horizontal, track.track[0][2]);
+        assert_eq!(Path::Empty, track.track[1][1]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let broken = |from: &str, to: &str| parse(&EXAMPLE.replacen(from, to, 1)).unwrap_err();
+        assert_eq!(
+            TrackError::DeadEnd(Position { x: 12, y: 2 }),
+            broken("v  |\n", "v   \n")
+        );
+        assert_eq!(
+            TrackError::BrokenCurve(Position { x: 7, y: 1 }),
+            broken("  /----", "  \\----")
+        );
+        assert_eq!(
+            TrackError::BrokenIntersection(Position { x: 3, y: 2 }),
+            broken("| /-+", "| /++")
+        );
+        assert_eq!(
+            TrackError::CartOffRail(Position { x: 9, y: 3 }),
+            broken("| v", "| >")
+        );
+        assert_eq!(
+            "cart at 9,3 isn't on a matching rail",
+            broken("| v", "| >").to_string()
+        );
+        // The cart's rail runs into the closed sides of both curves next to it.
+        assert_eq!(
+            TrackError::CartOffRail(Position { x: 2, y: 1 }),
+            parse("/\\ /\\\n\\/<\\/").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_tick() {
+        let mut track = parse(EXAMPLE).unwrap();
+        assert_eq!(EXAMPLE, track.to_string());
+        assert_eq!(
+            vec![
+                Event::Moved {
+                    cart: 0,
+                    from: Position { x: 2, y: 0 },
+                    to: Position { x: 3, y: 0 }
+                },
+                Event::Moved {
+                    cart: 1,
+                    from: Position { x: 9, y: 3 },
+                    to: Position { x: 9, y: 4 }
+                },
+            ],
+            track.tick()
+        );
+        assert_eq!(
+            Some(Event::Turned {
+                cart: 1,
+                at: Position { x: 9, y: 4 },
+                turn: Turn::Left
+            }),
+            track
+                .tick()
+                .into_iter()
+                .find(|e| matches!(e, Event::Turned { .. }))
+        );
+        for _ in 2..13 {
+            track.tick();
+        }
+        assert_eq!(
+            r#"/---\        
+|   |  /----\
+| /-+--v-\  |
+| | |  | |  |
+\-+-/  ^-+--/
+  \------/   "#,
+            track.to_string()
+        );
+        assert_eq!(
+            Some(&Event::Crashed {
+                at: Position { x: 7, y: 3 },
+                cart: 0,
+                other: 1
+            }),
+            track.tick().last()
+        );
+        assert_eq!(
+            r#"/---\        
+|   |  /----\
+| /-+--+-\  |
+| | |  X |  |
+\-+-/  \-+--/
+  \------/   "#,
+            track.to_string()
+        );
+    }
+
+    #[test]
+    fn test_turn_policies() {
+        let turns = |policy: &dyn TurnPolicy| (0..6).map(|n| policy.turn(n)).collect::<Vec<_>>();
+        use self::Turn::*;
+        assert_eq!(
+            vec![Left, Straight, Right, Left, Straight, Right],
+            turns(&Cycle::default())
+        );
+        assert_eq!(
+            vec![Right, Right, Left, Right, Right, Left],
+            turns(&Cycle::new(vec![Right, Right, Left]).unwrap())
+        );
+        assert_eq!(None, Cycle::new(vec![]));
+        assert_eq!(vec![Right; 6], turns(&Always(Right)));
+        assert_eq!(turns(&Random(7)), turns(&Random(7)));
+        let random: Vec<_> = (0..300).map(|n| Random(7).turn(n)).collect();
+        assert!([Left, Straight, Right]
+            .iter()
+            .all(|t| random.iter().filter(|r| r == &t).count() > 70));
+    }
+
+    #[test]
+    fn test_set_policy() {
+        let mut track = parse(EXAMPLE).unwrap();
+        track.set_policy(1, Arc::new(Always(Turn::Right)));
+        let turns: Vec<_> = (0..6)
+            .flat_map(|_| track.tick())
+            .filter_map(|e| match e {
+                Event::Turned { cart: 1, turn, .. } => Some(turn),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(vec![Turn::Right; 2], turns);
+
+        // Going straight keeps the carts on their own loops, while always turning left makes them
+        // meet sooner and somewhere else than the puzzle's carts do.
+        let crashes = |policy: Always| {
+            let mut track = parse(EXAMPLE).unwrap();
+            track.set_policies(Arc::new(policy));
+            let crashes: Vec<_> = (0..100)
+                .filter_map(|tick| track.step().map(|at| (tick, at)))
+                .collect();
+            (crashes, track.carts.len())
+        };
+        assert_eq!((vec![], 2), crashes(Always(Turn::Straight)));
+        assert_eq!(
+            (vec![(9, Position { x: 9, y: 1 })], 0),
+            crashes(Always(Turn::Left))
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Position { x: 7, y: 3 },
+            solve_part1(&parse(EXAMPLE).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let example2 = r#"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/"#;
+        assert_eq!(
+            Position { x: 6, y: 4 },
+            solve_part2(&parse(example2).unwrap())
+        );
+    }
+}
@@ -0,0 +1,1054 @@
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+type Coordinate = crate::coordinate::Coordinate<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Unit {
+    kind: UnitKind,
+    health: u32,
+    strength: u32,
+}
+
+/// A faction. Every faction fights every other one; besides elves and goblins, any other letter
+/// on the map is a faction of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitKind {
+    Goblin,
+    Elf,
+    Other(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub health: u32,
+    pub strength: u32,
+}
+
+/// Starting health and attack power for each faction. Factions without rules of their own use
+/// the puzzle's 200 HP and 3 attack power.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CombatRules {
+    factions: HashMap<UnitKind, Stats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cell {
+    Wall,
+    Empty,
+    Unit(UnitKind),
+}
+
+/// The starting map, along with the stats of any units that were given their own with the
+/// `G(200)` or `G(200, 3)` notation after their row.
+#[derive(Debug, PartialEq)]
+pub struct Grid(Vec<Vec<Cell>>, HashMap<Coordinate, UnitOverride>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct UnitOverride {
+    health: u32,
+    strength: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cave {
+    units: Vec<Unit>,
+    unit_positions: HashMap<Coordinate, usize>,
+    cells: Vec<Vec<Cell>>,
+    round: u32,
+    alive: HashMap<UnitKind, u32>,
+    events: Option<Vec<Event>>,
+}
+
+/// Something that happened during a battle. Units are identified by their index in reading
+/// order at the start of the battle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Moved {
+        unit: usize,
+        from: Coordinate,
+        to: Coordinate,
+    },
+    Attacked {
+        attacker: usize,
+        target: usize,
+        damage: u32,
+    },
+    Died {
+        unit: usize,
+        at: Coordinate,
+    },
+    RoundEnded {
+        round: u32,
+    },
+}
+
+impl UnitKind {
+    fn symbol(self) -> char {
+        match self {
+            UnitKind::Goblin => 'G',
+            UnitKind::Elf => 'E',
+            UnitKind::Other(c) => c,
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(from: char) -> Self {
+        match from {
+            '#' => Cell::Wall,
+            '.' => Cell::Empty,
+            'E' => Cell::Unit(UnitKind::Elf),
+            'G' => Cell::Unit(UnitKind::Goblin),
+            c if c.is_ascii_alphabetic() => Cell::Unit(UnitKind::Other(c)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl CombatRules {
+    pub fn with_faction(mut self, kind: UnitKind, health: u32, strength: u32) -> Self {
+        self.factions.insert(kind, Stats { health, strength });
+        self
+    }
+
+    pub fn with_strength(self, kind: UnitKind, strength: u32) -> Self {
+        let health = self.stats(kind).health;
+        self.with_faction(kind, health, strength)
+    }
+
+    pub fn stats(&self, kind: UnitKind) -> Stats {
+        self.factions.get(&kind).cloned().unwrap_or(Stats {
+            health: 200,
+            strength: 3,
+        })
+    }
+}
+
+impl Cave {
+    pub fn new(grid: &Grid, rules: &CombatRules) -> Self {
+        let cells = grid.0.clone();
+        let mut units = vec![];
+        let mut unit_positions = HashMap::new();
+        let mut alive = HashMap::new();
+        for (y, row) in cells.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Cell::Unit(kind) = cell {
+                    let coord = Coordinate { x, y };
+                    let mut stats = rules.stats(*kind);
+                    if let Some(unit) = grid.1.get(&coord) {
+                        stats.health = unit.health;
+                        stats.strength = unit.strength.unwrap_or(stats.strength);
+                    }
+                    *alive.entry(*kind).or_insert(0) += 1;
+                    units.push(Unit {
+                        kind: *kind,
+                        health: stats.health,
+                        strength: stats.strength,
+                    });
+                    unit_positions.insert(coord, units.len() - 1);
+                }
+            }
+        }
+        Cave {
+            units,
+            unit_positions,
+            cells,
+            round: 0,
+            alive,
+            events: None,
+        }
+    }
+
+    /// Whether at most one faction is left standing.
+    pub fn is_over(&self) -> bool {
+        self.alive.values().filter(|&&count| count > 0).count() <= 1
+    }
+
+    pub fn alive(&self, kind: UnitKind) -> u32 {
+        self.alive.get(&kind).cloned().unwrap_or(0)
+    }
+
+    /// Starts keeping a log of everything that happens, to be collected with `take_events`.
+    pub fn record_events(&mut self) {
+        self.events.get_or_insert_with(Vec::new);
+    }
+
+    pub fn take_events(&mut self) -> Vec<Event> {
+        self.events.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn record(&mut self, event: Event) {
+        if let Some(events) = self.events.as_mut() {
+            events.push(event);
+        }
+    }
+
+    /// Plays out at least one round. Once units stop moving, this skips ahead through the rounds
+    /// where they just trade blows, stopping before one of them would die.
+    pub fn tick(&mut self) {
+        if self.step() || self.is_over() {
+            return;
+        }
+
+        // If a move didn't happen, then nobody will move again until a unit dies. Play out the
+        // rounds where units just trade blows without searching for moves, stopping before one
+        // where something would die.
+        let mut turn_order: Vec<_> = self
+            .unit_positions
+            .iter()
+            .map(|(&coord, &index)| (coord, index))
+            .collect();
+        turn_order.sort();
+        loop {
+            // First work out who attacks whom, and end early if anything will die so we can
+            // simulate that round in full. Targets are picked by health, which changes as the
+            // round goes on, so track it as we go.
+            let mut temp_health: Vec<_> = self.units.iter().map(|u| u.health).collect();
+            let mut attacks = vec![];
+            for &(coord, index) in &turn_order {
+                let kind = self.units[index].kind;
+                let target = self.find_attack_by(coord, kind, |i| temp_health[i]);
+                if let Some((target_coord, target_index)) = target {
+                    let strength = self.units[index].strength;
+                    temp_health[target_index] = temp_health[target_index].saturating_sub(strength);
+                    if temp_health[target_index] == 0 {
+                        return;
+                    }
+                    attacks.push((index, target_index, target_coord));
+                }
+            }
+            if attacks.is_empty() {
+                // Nobody can reach anybody; the battle will never end.
+                return;
+            }
+
+            // Now actually deal damage for this round.
+            for (attacker_index, target_index, target_coord) in attacks {
+                self.do_attack(attacker_index, target_index, target_coord);
+            }
+            self.end_round();
+        }
+    }
+
+    /// Plays out a single round exactly as the puzzle describes it, returning whether any unit
+    /// moved or died.
+    pub fn step(&mut self) -> bool {
+        let mut waiting_units = self.collect_waiting_units();
+        let mut move_happened = false;
+        let mut unit_died = false;
+        while let Some(Reverse((mut coord, index))) = waiting_units.pop() {
+            if self.is_over() {
+                return move_happened || unit_died;
+            }
+            if self.units[index].health == 0 {
+                // Unit died, skip it.
+                continue;
+            }
+            let kind = self.units[index].kind;
+            if let Some(movement) = self.find_move(coord, kind) {
+                self.move_unit(index, coord, movement);
+                self.record(Event::Moved {
+                    unit: index,
+                    from: coord,
+                    to: movement,
+                });
+                coord = movement;
+                move_happened = true;
+            }
+            if let Some((attack_coord, unit_index)) = self.find_attack(coord, kind) {
+                unit_died = self.do_attack(index, unit_index, attack_coord) || unit_died;
+            }
+        }
+        self.end_round();
+        move_happened || unit_died
+    }
+
+    fn collect_waiting_units(&self) -> BinaryHeap<Reverse<(Coordinate, usize)>> {
+        self.unit_positions
+            .iter()
+            .map(|(&c, &i)| Reverse((c, i)))
+            .collect()
+    }
+
+    fn find_move(&self, coord: Coordinate, kind: UnitKind) -> Option<Coordinate> {
+        type Distance = usize;
+        type FirstMove = Coordinate;
+        let mut open: BinaryHeap<Reverse<(Distance, Coordinate, FirstMove)>> = BinaryHeap::new();
+        let mut visited = HashMap::new();
+        visited.insert(coord, (0, coord));
+        for neighbor in self.neighbors(coord) {
+            if let Cell::Unit(other_kind) = self.cells[neighbor.y][neighbor.x] {
+                if kind != other_kind {
+                    // If we are already next to a unit of the other kind, don't move.
+                    return None;
+                }
+            } else if self.cells[neighbor.y][neighbor.x] == Cell::Empty {
+                open.push(Reverse((1, neighbor, neighbor)));
+                visited.insert(neighbor, (1, neighbor));
+            }
+        }
+        while let Some(Reverse((distance, next_coord, first_move))) = open.pop() {
+            if let Cell::Unit(other_kind) = self.cells[next_coord.y][next_coord.x] {
+                if kind != other_kind {
+                    // We found a reachable unit of the other kind, so return the first move in that direction.
+                    return Some(first_move);
+                }
+            }
+            for neighbor in self.neighbors(next_coord) {
+                let skip = match visited.entry(neighbor) {
+                    Entry::Vacant(_) => false,
+                    Entry::Occupied(ref entry) => {
+                        let (existing_distance, existing_first_move) = entry.get();
+                        distance > *existing_distance || first_move >= *existing_first_move
+                    }
+                };
+                if skip {
+                    continue;
+                }
+                let explore = match self.cells[neighbor.y][neighbor.x] {
+                    Cell::Empty => true,
+                    Cell::Unit(other_kind) if kind != other_kind => true,
+                    _ => false,
+                };
+                if explore {
+                    open.push(Reverse((distance + 1, neighbor, first_move)));
+                    visited.insert(neighbor, (distance + 1, first_move));
+                }
+            }
+        }
+        None
+    }
+
+    fn find_attack(&self, coord: Coordinate, kind: UnitKind) -> Option<(Coordinate, usize)> {
+        self.find_attack_by(coord, kind, |index| self.units[index].health)
+    }
+
+    fn find_attack_by(
+        &self,
+        coord: Coordinate,
+        kind: UnitKind,
+        health: impl Fn(usize) -> u32,
+    ) -> Option<(Coordinate, usize)> {
+        let mut attack = None;
+        let mut min_health = u32::MAX;
+        for neighbor in self.neighbors(coord) {
+            if let Cell::Unit(other_kind) = self.cells[neighbor.y][neighbor.x] {
+                if kind != other_kind {
+                    let index = self.unit_positions[&neighbor];
+                    if health(index) < min_health || attack.is_none() {
+                        attack = Some((neighbor, index));
+                        min_health = health(index);
+                    }
+                }
+            }
+        }
+        attack
+    }
+
+    fn do_attack(
+        &mut self,
+        attacker_index: usize,
+        target_index: usize,
+        target_coord: Coordinate,
+    ) -> bool {
+        let strength = self.units[attacker_index].strength;
+        let damage = std::cmp::min(strength, self.units[target_index].health);
+        self.record(Event::Attacked {
+            attacker: attacker_index,
+            target: target_index,
+            damage,
+        });
+        self.damage_unit(target_index, damage);
+        if self.units[target_index].health == 0 {
+            self.remove_unit(target_coord);
+            self.record(Event::Died {
+                unit: target_index,
+                at: target_coord,
+            });
+            return true;
+        }
+        false
+    }
+
+    fn end_round(&mut self) {
+        self.round += 1;
+        let round = self.round;
+        self.record(Event::RoundEnded { round });
+    }
+
+    fn move_unit(&mut self, index: usize, from: Coordinate, to: Coordinate) {
+        let kind = self.units[index].kind;
+        self.unit_positions.remove(&from);
+        self.unit_positions.insert(to, index);
+        self.cells[from.y][from.x] = Cell::Empty;
+        self.cells[to.y][to.x] = Cell::Unit(kind);
+    }
+
+    fn damage_unit(&mut self, index: usize, damage: u32) {
+        let unit = &mut self.units[index];
+        unit.health = unit.health.saturating_sub(damage);
+    }
+
+    fn remove_unit(&mut self, coord: Coordinate) {
+        let index = self.unit_positions.remove(&coord).unwrap();
+        self.cells[coord.y][coord.x] = Cell::Empty;
+        *self.alive.get_mut(&self.units[index].kind).unwrap() -= 1;
+    }
+
+    /// Applies an event recorded from another battle, as if it had happened here.
+    fn apply(&mut self, event: &Event) {
+        match *event {
+            Event::Moved { unit, from, to } => self.move_unit(unit, from, to),
+            Event::Attacked { target, damage, .. } => self.damage_unit(target, damage),
+            Event::Died { at, .. } => self.remove_unit(at),
+            Event::RoundEnded { round } => self.round = round,
+        }
+    }
+
+    fn neighbors(&self, coord: Coordinate) -> impl Iterator<Item = Coordinate> {
+        let xlen = self.cells[0].len();
+        let ylen = self.cells.len();
+        [(0, -1), (-1, 0), (1, 0), (0, 1)]
+            .iter()
+            .cloned()
+            .filter_map(move |(dx, dy)| {
+                let x = coord.x as isize + dx;
+                let y = coord.y as isize + dy;
+                if x < 0 || y < 0 {
+                    return None;
+                }
+                let (x, y) = (x as usize, y as usize);
+                if y >= ylen || x >= xlen {
+                    return None;
+                }
+                Some(Coordinate { x, y })
+            })
+    }
+}
+
+impl std::fmt::Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (y, row) in self.cells.iter().enumerate() {
+            let mut healths = vec![];
+            for (x, cell) in row.iter().enumerate() {
+                write!(
+                    f,
+                    "{}",
+                    match cell {
+                        Cell::Empty => '.',
+                        Cell::Wall => '#',
+                        Cell::Unit(kind) => {
+                            let ch = kind.symbol();
+                            let index = self.unit_positions[&Coordinate { y, x }];
+                            healths.push(format!("{}({})", ch, self.units[index].health));
+                            ch
+                        }
+                    }
+                )?;
+            }
+            if !healths.is_empty() {
+                write!(f, "   {}", healths.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays `events` on top of `initial`, drawing the cave at the end of every round in the same
+/// layout as the puzzle's examples.
+pub fn replay(initial: &Cave, events: &[Event]) -> Vec<String> {
+    let mut cave = initial.clone();
+    cave.events = None;
+    let mut frames = vec![];
+    for event in events {
+        cave.apply(event);
+        if let Event::RoundEnded { round } = event {
+            let plural = if *round == 1 { "" } else { "s" };
+            frames.push(format!("After {} round{}:\n{}", round, plural, cave));
+        }
+    }
+    frames
+}
+
+/// The first round where `tick` and the plain round-by-round simulation disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub round: u32,
+    pub optimized: String,
+    pub naive: String,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "simulations diverge after round {}\noptimized:\n{}naive:\n{}",
+            self.round, self.optimized, self.naive
+        )
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Fights the battle with `tick`, while also playing it out one `step` at a time, and checks
+/// that both agree at the end of every round. Returns the outcome if they do.
+pub fn verify(grid: &Grid, rules: &CombatRules) -> Result<u32, Divergence> {
+    lockstep(grid, rules, Cave::tick)
+}
+
+fn lockstep(grid: &Grid, rules: &CombatRules, tick: fn(&mut Cave)) -> Result<u32, Divergence> {
+    fn compare(optimized: &Cave, naive: &Cave) -> Result<(), Divergence> {
+        let (left, right) = (optimized.to_string(), naive.to_string());
+        if optimized.round != naive.round || left != right {
+            return Err(Divergence {
+                round: naive.round,
+                optimized: left,
+                naive: right,
+            });
+        }
+        Ok(())
+    }
+
+    let mut optimized = Cave::new(grid, rules);
+    let mut naive = optimized.clone();
+    // `tick` may play out many rounds at once, so follow along with its events to see the state
+    // at the end of each one.
+    let mut replayed = optimized.clone();
+    optimized.record_events();
+    while !optimized.is_over() {
+        tick(&mut optimized);
+        for event in optimized.take_events() {
+            replayed.apply(&event);
+            if let Event::RoundEnded { .. } = event {
+                naive.step();
+                compare(&replayed, &naive)?;
+            }
+        }
+    }
+    // Combat ends partway through a round, which the naive simulation hasn't played yet.
+    while !naive.is_over() {
+        naive.step();
+    }
+    compare(&optimized, &naive)?;
+    Ok(outcome(&naive))
+}
+
+/// Stats after a row of the map that don't line up with the units in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverrideError {
+    pub line: usize,
+    pub text: String,
+    pub reason: &'static str,
+}
+
+impl std::fmt::Display for OverrideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {} in {:?}", self.line, self.reason, self.text)
+    }
+}
+
+impl std::error::Error for OverrideError {}
+
+/// Parses the map. A row can be followed by stats for its units, like `G(150), E(80, 10)` to give
+/// the goblin 150 hit points and the elf 80 hit points and an attack power of 10. If there are
+/// any, there has to be one for each unit in the row, in order.
+#[aoc_generator(day15)]
+pub fn parse(input: &str) -> Result<Box<Grid>, OverrideError> {
+    lazy_static! {
+        static ref OVERRIDE: Regex = Regex::new(r"([A-Za-z])\((\d+)(?:,\s*(\d+))?\)").unwrap();
+    }
+    let mut cells = vec![];
+    let mut overrides = HashMap::new();
+    for (y, line) in input.lines().enumerate() {
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let row: Vec<Cell> = parts.next().unwrap().chars().map(Cell::from).collect();
+        let stats = parts.next().unwrap_or("").trim();
+        if !stats.is_empty() {
+            let bad = |text: &str, reason| OverrideError {
+                line: y + 1,
+                text: text.to_owned(),
+                reason,
+            };
+            // Only commas and spaces can come between the stats.
+            let separator = |text: &str| text.chars().all(|c| c == ',' || c.is_whitespace());
+            let mut units = row.iter().enumerate().filter_map(|(x, cell)| match cell {
+                Cell::Unit(kind) => Some((Coordinate { x, y }, kind.symbol())),
+                _ => None,
+            });
+            let mut end = 0;
+            for caps in OVERRIDE.captures_iter(stats) {
+                let text = &caps[0];
+                let start = caps.get(0).unwrap().start();
+                if !separator(&stats[end..start]) {
+                    return Err(bad(stats, "expected stats like G(200) or G(200, 3)"));
+                }
+                end = start + text.len();
+                let (coord, symbol) = units
+                    .next()
+                    .ok_or_else(|| bad(stats, "more stats than units"))?;
+                if caps[1] != symbol.to_string() {
+                    return Err(bad(text, "stats for a different kind of unit"));
+                }
+                let number = |m: regex::Match| {
+                    m.as_str()
+                        .parse()
+                        .map_err(|_| bad(text, "number out of range"))
+                };
+                let unit = UnitOverride {
+                    health: number(caps.get(2).unwrap())?,
+                    strength: caps.get(3).map(number).transpose()?,
+                };
+                overrides.insert(coord, unit);
+            }
+            if !separator(&stats[end..]) {
+                return Err(bad(stats, "expected stats like G(200) or G(200, 3)"));
+            }
+            if units.next().is_some() {
+                return Err(bad(stats, "fewer stats than units"));
+            }
+        }
+        cells.push(row);
+    }
+    Ok(Box::new(Grid(cells, overrides)))
+}
+
+fn outcome(cave: &Cave) -> u32 {
+    let hitpoints: u32 = cave.units.iter().map(|u| u.health).sum();
+    hitpoints * cave.round
+}
+
+#[aoc(day15, part1)]
+fn solve_part1(grid: &Grid) -> u32 {
+    let mut cave = Cave::new(grid, &CombatRules::default());
+    while !cave.is_over() {
+        cave.tick();
+    }
+    outcome(&cave)
+}
+
+#[aoc(day15, part1, verified)]
+fn solve_part1_verified(grid: &Grid) -> Result<u32, Divergence> {
+    verify(grid, &CombatRules::default())
+}
+
+/// How a battle went for the elves with some attack power.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// At least one elf died, so the battle was called off.
+    ElfDied,
+    /// Every elf made it, leaving `hitpoints` between them after `rounds` full rounds.
+    Won { rounds: u32, hitpoints: u32 },
+}
+
+/// The outcome shared by a range of elf attack powers that all fight the same battle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trial {
+    pub strengths: RangeInclusive<u32>,
+    pub outcome: Outcome,
+}
+
+/// Groups elf attack powers from 4 upwards into ranges that fight identical battles. If every
+/// enemy starts with the same health, every elf uses the faction's attack power, and the only
+/// enemies are goblins, then what matters is how many hits it takes to kill an enemy: enemies are
+/// still picked off in the same order, and die on the same turns. A third faction fighting the
+/// goblins too would leave them with health that depends on exactly how hard the elves hit.
+/// Past the point where one hit kills anything, nothing changes.
+fn strength_ranges(grid: &Grid, rules: &CombatRules) -> Vec<RangeInclusive<u32>> {
+    let probe = Cave::new(grid, rules);
+    let enemy_health: Vec<_> = probe
+        .units
+        .iter()
+        .filter(|u| u.kind != UnitKind::Elf)
+        .map(|u| u.health)
+        .collect();
+    let max_health = enemy_health.iter().cloned().max().unwrap_or(0);
+    let elf_overrides = grid.1.iter().any(|(coord, unit)| {
+        unit.strength.is_some() && grid.0[coord.y][coord.x] == Cell::Unit(UnitKind::Elf)
+    });
+    let third_faction = probe
+        .units
+        .iter()
+        .any(|u| matches!(u.kind, UnitKind::Other(_)));
+    let uniform = !elf_overrides && !third_faction && enemy_health.iter().all(|&h| h == max_health);
+
+    let mut ranges = vec![];
+    let mut strength = 4;
+    loop {
+        if strength >= max_health {
+            ranges.push(strength..=strength);
+            return ranges;
+        }
+        let end = if uniform {
+            let hits = max_health.div_ceil(strength);
+            (max_health - 1) / (hits - 1)
+        } else {
+            strength
+        };
+        ranges.push(strength..=end);
+        strength = end + 1;
+    }
+}
+
+fn fight(grid: &Grid, rules: &CombatRules, elf_strength: u32) -> Outcome {
+    let rules = rules.clone().with_strength(UnitKind::Elf, elf_strength);
+    let mut cave = Cave::new(grid, &rules);
+    let initial_elves = cave.alive(UnitKind::Elf);
+    while !cave.is_over() {
+        cave.tick();
+        if cave.alive(UnitKind::Elf) < initial_elves {
+            return Outcome::ElfDied;
+        }
+    }
+    Outcome::Won {
+        rounds: cave.round,
+        hitpoints: cave.units.iter().map(|u| u.health).sum(),
+    }
+}
+
+/// Tries increasing elf attack powers until the elves win without losing anyone, fighting one
+/// battle per range of equivalent attack powers and several of those at once. Returns every
+/// trial that was fought, in order; the last winning trial gives the answer.
+pub fn search_elf_strength(grid: &Grid, rules: &CombatRules) -> Vec<Trial> {
+    let mut table = vec![];
+    for batch in strength_ranges(grid, rules).chunks(rayon::current_num_threads()) {
+        let trials: Vec<_> = batch
+            .par_iter()
+            .map(|strengths| Trial {
+                strengths: strengths.clone(),
+                outcome: fight(grid, rules, *strengths.start()),
+            })
+            .collect();
+        let won = trials.iter().position(|t| t.outcome != Outcome::ElfDied);
+        match won {
+            Some(index) => {
+                table.extend(trials.into_iter().take(index + 1));
+                break;
+            }
+            None => table.extend(trials),
+        }
+    }
+    table
+}
+
+#[aoc(day15, part2)]
+fn solve_part2(grid: &Grid) -> u32 {
+    let table = search_elf_strength(grid, &CombatRules::default());
+    match table.last().map(|trial| trial.outcome) {
+        Some(Outcome::Won { rounds, hitpoints }) => rounds * hitpoints,
+        _ => panic!("the elves can't win without losses"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLES: &[&str] = &[
+        "#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######",
+        "#######\n#G..#E#\n#E#E.E#\n#G.##.#\n#...#E#\n#...E.#\n#######",
+        "#######\n#E..EG#\n#.#G.E#\n#E.##E#\n#G..#.#\n#..E#.#\n#######",
+        "#######\n#E.G#.#\n#.#G..#\n#G.#.G#\n#G..#.#\n#...E.#\n#######",
+        "#######\n#.E...#\n#.#..G#\n#.###.#\n#E#G#G#\n#...#G#\n#######",
+        "#########\n#G......#\n#.E.#...#\n#..##..G#\n#...##..#\n#...#...#\n#.G...G.#\n#.....G.#\n#########",
+    ];
+
+    #[test]
+    fn test_parse() {
+        let cells = parse("###\n#.E\n#G.").unwrap().0;
+        assert_eq!(
+            cells,
+            vec![
+                vec![Cell::Wall; 3],
+                vec![Cell::Wall, Cell::Empty, Cell::Unit(UnitKind::Elf)],
+                vec![Cell::Wall, Cell::Unit(UnitKind::Goblin), Cell::Empty]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        let grid = parse("#######\n#.G.E.#   G(150), E(80, 10)\n#######").unwrap();
+        assert_eq!(
+            Some(&UnitOverride {
+                health: 150,
+                strength: None
+            }),
+            grid.1.get(&Coordinate { x: 2, y: 1 })
+        );
+        assert_eq!(
+            Some(&UnitOverride {
+                health: 80,
+                strength: Some(10)
+            }),
+            grid.1.get(&Coordinate { x: 4, y: 1 })
+        );
+        assert_eq!(2, grid.1.len());
+    }
+
+    #[test]
+    fn test_parse_override_errors() {
+        let error = |stats: &str| parse(&format!("#######\n#.G.E.#   {}", stats)).unwrap_err();
+        assert_eq!(
+            OverrideError {
+                line: 2,
+                text: "E(150)".to_owned(),
+                reason: "stats for a different kind of unit",
+            },
+            error("E(150), G(80)")
+        );
+        assert_eq!(
+            "line 2: number out of range in \"E(80, 99999999999)\"",
+            error("G(150), E(80, 99999999999)").to_string()
+        );
+        assert_eq!("more stats than units", error("G(150), E(80), G(3)").reason);
+        assert_eq!("fewer stats than units", error("G(150)").reason);
+        assert_eq!(
+            "expected stats like G(200) or G(200, 3)",
+            error("G(150), E(eighty)").reason
+        );
+        assert_eq!(
+            "expected stats like G(200) or G(200, 3)",
+            error("G(150) E(80) oops").reason
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = CombatRules::default().with_faction(UnitKind::Goblin, 200, 200);
+        let mut cave = Cave::new(&parse("#####\n#E.G#\n#####").unwrap(), &rules);
+        while !cave.is_over() {
+            cave.tick();
+        }
+        assert_eq!(197, outcome(&cave));
+
+        // Dwarves hit hard, but everyone gangs up on them.
+        let rules = CombatRules::default().with_faction(UnitKind::Other('D'), 200, 1000);
+        let mut cave = Cave::new(&parse("#####\n#EDG#\n#####").unwrap(), &rules);
+        cave.tick();
+        assert!(!cave.is_over());
+        assert_eq!(0, cave.alive(UnitKind::Elf));
+        cave.tick();
+        assert!(cave.is_over());
+        assert_eq!(1, cave.alive(UnitKind::Other('D')));
+        assert_eq!(0, cave.alive(UnitKind::Goblin));
+
+        let grid = parse("#####\n#EDG#   E(10), D(300, 1), G(5, 10)\n#####").unwrap();
+        let mut cave = Cave::new(&grid, &rules);
+        cave.tick();
+        // Nobody moves or dies at first, so the tick carries on until the goblin is about to.
+        assert_eq!(4, cave.round);
+        assert_eq!(
+            "#####\n#EDG#   E(10), D(248), G(1)\n#####\n",
+            cave.to_string()
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(27730, solve_part1(&parse(EXAMPLES[0]).unwrap()));
+        assert_eq!(36334, solve_part1(&parse(EXAMPLES[1]).unwrap()));
+        assert_eq!(39514, solve_part1(&parse(EXAMPLES[2]).unwrap()));
+        assert_eq!(27755, solve_part1(&parse(EXAMPLES[3]).unwrap()));
+        assert_eq!(28944, solve_part1(&parse(EXAMPLES[4]).unwrap()));
+        assert_eq!(18740, solve_part1(&parse(EXAMPLES[5]).unwrap()));
+    }
+
+    #[test]
+    fn test_verify() {
+        for example in EXAMPLES {
+            let grid = parse(example).unwrap();
+            assert_eq!(
+                Ok(solve_part1(&grid)),
+                verify(&grid, &CombatRules::default())
+            );
+        }
+
+        // The elf in the middle should switch to the goblin on the right after three rounds,
+        // once it has taken more damage than the one on the left.
+        let grid = parse("#######\n#.GEGE#   G(100), E(200), G(120), E(200, 10)\n#######").unwrap();
+        assert!(verify(&grid, &CombatRules::default()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_reports_divergence() {
+        fn bad_tick(cave: &mut Cave) {
+            cave.step();
+            cave.record(Event::Attacked {
+                attacker: 0,
+                target: 1,
+                damage: 1,
+            });
+        }
+        let grid = parse(EXAMPLES[0]).unwrap();
+        let divergence = lockstep(&grid, &CombatRules::default(), bad_tick).unwrap_err();
+        assert_eq!(2, divergence.round);
+        assert!(divergence.optimized.contains("E(187)"));
+        assert!(divergence.naive.contains("E(188)"));
+    }
+
+    #[test]
+    fn test_strength_ranges() {
+        let ranges = strength_ranges(&parse(EXAMPLES[0]).unwrap(), &CombatRules::default());
+        assert_eq!(Some(&(4..=4)), ranges.first());
+        assert_eq!(Some(&(14..=14)), ranges.iter().find(|r| r.contains(&14)));
+        assert_eq!(Some(&(34..=39)), ranges.iter().find(|r| r.contains(&34)));
+        assert_eq!(Some(&(100..=199)), ranges.iter().find(|r| r.contains(&150)));
+        assert_eq!(Some(&(200..=200)), ranges.last());
+
+        // Goblins with less health than the rest break the equivalence.
+        let grid = parse("#######\n#.G...#   G(150)\n#...EG#\n#######").unwrap();
+        let ranges = strength_ranges(&grid, &CombatRules::default());
+        assert!(ranges.iter().all(|r| r.start() == r.end()));
+        assert_eq!(197, ranges.len());
+    }
+
+    #[test]
+    fn test_search_elf_strength_three_factions() {
+        let grid = parse("#######\n#.G...#\n#...EG#\n#.#.#G#\n#..D#E#\n#.....#\n#######").unwrap();
+        let rules = CombatRules::default().with_faction(UnitKind::Other('D'), 200, 5);
+        // The same answer as trying every attack power in turn.
+        let (strength, outcome) = (4..)
+            .map(|s| (s, fight(&grid, &rules, s)))
+            .find(|(_, outcome)| *outcome != Outcome::ElfDied)
+            .unwrap();
+        let last = search_elf_strength(&grid, &rules).pop().unwrap();
+        assert_eq!(strength..=strength, last.strengths);
+        assert_eq!(outcome, last.outcome);
+        let ranges = strength_ranges(&grid, &rules);
+        assert!(ranges.iter().all(|r| r.start() == r.end()));
+    }
+
+    #[test]
+    fn test_search_elf_strength() {
+        let table = search_elf_strength(&parse(EXAMPLES[0]).unwrap(), &CombatRules::default());
+        assert!(table[..table.len() - 1]
+            .iter()
+            .all(|t| t.outcome == Outcome::ElfDied));
+        let last = table.last().unwrap();
+        assert_eq!(15..=15, last.strengths);
+        assert_eq!(
+            Outcome::Won {
+                rounds: 29,
+                hitpoints: 172
+            },
+            last.outcome
+        );
+        // Every attack power in a range really does fight the same battle.
+        let rules = CombatRules::default();
+        for trial in &table {
+            for strength in trial.strengths.clone() {
+                assert_eq!(
+                    trial.outcome,
+                    fight(&parse(EXAMPLES[0]).unwrap(), &rules, strength)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(4988, solve_part2(&parse(EXAMPLES[0]).unwrap()));
+        assert_eq!(31284, solve_part2(&parse(EXAMPLES[2]).unwrap()));
+        assert_eq!(3478, solve_part2(&parse(EXAMPLES[3]).unwrap()));
+        assert_eq!(6474, solve_part2(&parse(EXAMPLES[4]).unwrap()));
+        assert_eq!(1140, solve_part2(&parse(EXAMPLES[5]).unwrap()));
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut cave = Cave::new(&parse(EXAMPLES[0]).unwrap(), &CombatRules::default());
+        let initial = cave.clone();
+        cave.record_events();
+        while !cave.is_over() {
+            cave.tick();
+        }
+        let events = cave.take_events();
+        assert_eq!(
+            Some(&Event::Died {
+                unit: 1,
+                at: Coordinate { x: 4, y: 2 }
+            }),
+            events.iter().find(|e| matches!(e, Event::Died { .. }))
+        );
+
+        let frames = replay(&initial, &events);
+        assert_eq!(47, frames.len());
+        assert_eq!(
+            "After 1 round:
+#######
+#..G..#   G(200)
+#...EG#   E(197), G(197)
+#.#G#G#   G(200), G(197)
+#...#E#   E(197)
+#.....#
+#######
+",
+            frames[0]
+        );
+        assert_eq!(
+            "After 2 rounds:
+#######
+#...G.#   G(200)
+#..GEG#   G(200), E(188), G(194)
+#.#.#G#   G(194)
+#...#E#   E(194)
+#.....#
+#######
+",
+            frames[1]
+        );
+        assert_eq!(
+            "After 23 rounds:
+#######
+#...G.#   G(200)
+#..G.G#   G(200), G(131)
+#.#.#G#   G(131)
+#...#E#   E(131)
+#.....#
+#######
+",
+            frames[22]
+        );
+        assert_eq!(
+            "After 28 rounds:
+#######
+#G....#   G(200)
+#.G...#   G(131)
+#.#.#G#   G(116)
+#...#E#   E(113)
+#....G#   G(200)
+#######
+",
+            frames[27]
+        );
+        assert_eq!(
+            "After 47 rounds:
+#######
+#G....#   G(200)
+#.G...#   G(131)
+#.#.#G#   G(59)
+#...#.#
+#....G#   G(200)
+#######
+",
+            frames[46]
+        );
+        assert_eq!(
+            format!("{}", cave),
+            frames[46]["After 47 rounds:\n".len()..]
+        );
+
+        // Frames can be read back in to pick up the battle from where they left off.
+        let frame = &frames[1]["After 2 rounds:\n".len()..];
+        let resumed = Cave::new(&parse(frame).unwrap(), &CombatRules::default());
+        assert_eq!(frame, resumed.to_string());
+    }
+}
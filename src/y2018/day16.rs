@@ -0,0 +1,215 @@
+use crate::elfcode::{self, Opcode};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Registers([usize; 4]);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Instruction {
+    opcode: u8,
+    a: usize,
+    b: usize,
+    c: usize,
+}
+
+trait Interpet {
+    fn interpret(&self, inst: Instruction) -> elfcode::Instruction;
+}
+
+impl Registers {
+    fn execute(self, instruction: elfcode::Instruction) -> Self {
+        let mut out = self;
+        instruction.execute(&mut out.0);
+        out
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Sample {
+    before: Registers,
+    instruction: Instruction,
+    after: Registers,
+}
+
+struct Input {
+    samples: Vec<Sample>,
+    program: Vec<Instruction>,
+}
+
+impl Instruction {
+    fn new(opcode: u8, a: usize, b: usize, c: usize) -> Self {
+        Instruction { opcode, a, b, c }
+    }
+}
+
+impl Interpet for Opcode {
+    fn interpret(&self, inst: Instruction) -> elfcode::Instruction {
+        self.instruction(inst.a, inst.b, inst.c)
+    }
+}
+
+impl Interpet for [Opcode; 16] {
+    fn interpret(&self, inst: Instruction) -> elfcode::Instruction {
+        self[inst.opcode as usize].interpret(inst)
+    }
+}
+
+#[aoc_generator(day16)]
+fn parse(input: &str) -> Box<Input> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r"(Before:\s*\[(\d+), (\d+), (\d+), (\d+)\]\s*(\d+) (\d+) (\d+) (\d+)\s*After:\s*\[(\d+), (\d+), (\d+), (\d+)\])|(\n(\d+) (\d+) (\d+) (\d+))")
+            .unwrap();
+    }
+    let mut samples = vec![];
+    let mut program = vec![];
+    for caps in RE.captures_iter(input) {
+        if caps.get(1).is_some() {
+            // Sample
+            samples.push(Sample {
+                before: Registers([
+                    caps[2].parse().unwrap(),
+                    caps[3].parse().unwrap(),
+                    caps[4].parse().unwrap(),
+                    caps[5].parse().unwrap(),
+                ]),
+                instruction: Instruction::new(
+                    caps[6].parse().unwrap(),
+                    caps[7].parse().unwrap(),
+                    caps[8].parse().unwrap(),
+                    caps[9].parse().unwrap(),
+                ),
+                after: Registers([
+                    caps[10].parse().unwrap(),
+                    caps[11].parse().unwrap(),
+                    caps[12].parse().unwrap(),
+                    caps[13].parse().unwrap(),
+                ]),
+            })
+        } else {
+            // Part of the program
+            program.push(Instruction::new(
+                caps[15].parse().unwrap(),
+                caps[16].parse().unwrap(),
+                caps[17].parse().unwrap(),
+                caps[18].parse().unwrap(),
+            ));
+        }
+    }
+    Box::new(Input { samples, program })
+}
+
+fn all_opcodes() -> impl Iterator<Item = Opcode> {
+    Opcode::ALL.iter().cloned()
+}
+
+#[aoc(day16, part1)]
+fn solve_part1(input: &Input) -> u32 {
+    let mut count = 0;
+    for sample in &input.samples {
+        let mut valid_count = 0;
+        for inst in all_opcodes().map(|op| op.interpret(sample.instruction)) {
+            if sample.before.execute(inst) == sample.after {
+                valid_count += 1;
+            }
+            if valid_count >= 3 {
+                count += 1;
+                break;
+            }
+        }
+    }
+    count
+}
+
+#[aoc(day16, part2)]
+fn solve_part2(input: &Input) -> usize {
+    let mut possible_codes: HashMap<Opcode, HashSet<u8>> =
+        all_opcodes().map(|op| (op, (0..16).collect())).collect();
+    let mut broken = false;
+    for sample in &input.samples {
+        for op in all_opcodes() {
+            let inst = op.interpret(sample.instruction);
+            if sample.before.execute(inst) != sample.after {
+                possible_codes
+                    .get_mut(&op)
+                    .unwrap()
+                    .remove(&sample.instruction.opcode);
+                if possible_codes[&op].len() == 1 {
+                    broken = true;
+                }
+            }
+        }
+        if broken {
+            break;
+        }
+    }
+    let mut opcode_table = [Opcode::Addi; 16];
+    let mut assigned = 0;
+    while assigned < 16 {
+        let known_codes: Vec<_> = possible_codes
+            .iter()
+            .filter_map(|(op, codes)| {
+                if codes.len() == 1 {
+                    Some((*op, *codes.iter().next().unwrap()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for (op, code) in known_codes {
+            assigned += 1;
+            opcode_table[code as usize] = op;
+            for remaining in possible_codes.values_mut() {
+                remaining.remove(&code);
+            }
+        }
+    }
+    let mut registers = Registers([0, 0, 0, 0]);
+    for inst in &input.program {
+        registers = registers.execute(opcode_table.interpret(*inst));
+    }
+    registers.0[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "
+Before: [2, 0, 2, 2]
+7 3 2 0
+After:  [0, 0, 2, 2]
+
+Before: [3, 1, 1, 0]
+5 2 1 2
+After:  [3, 1, 2, 0]
+
+
+2 2 3 3
+2 0 3 2";
+        let input = parse(input);
+        assert_eq!(
+            input.program,
+            vec![Instruction::new(2, 2, 3, 3), Instruction::new(2, 0, 3, 2)]
+        );
+        assert_eq!(
+            input.samples,
+            vec![
+                Sample {
+                    before: Registers([2, 0, 2, 2]),
+                    instruction: Instruction::new(7, 3, 2, 0),
+                    after: Registers([0, 0, 2, 2])
+                },
+                Sample {
+                    before: Registers([3, 1, 1, 0]),
+                    instruction: Instruction::new(5, 2, 1, 2),
+                    after: Registers([3, 1, 2, 0])
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,529 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cmp;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+pub type Coordinate = crate::coordinate::Coordinate<usize>;
+
+/// Where the puzzle's spring is.
+const SPRING: Coordinate = Coordinate { x: 500, y: 0 };
+
+#[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
+pub enum Cell {
+    Sand,
+    Clay,
+    WetSand,
+    Water,
+}
+
+/// The scanned slice of ground. Cells are stored for a rectangle starting at
+/// (`offsetx`, `offsety`), which grows as needed to take in springs; anything outside it is sand.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    cells: Vec<Vec<Cell>>,
+    offsetx: usize,
+    offsety: usize,
+    /// The rows that count towards the totals, from the highest clay to the bottom.
+    scanned: RangeInclusive<usize>,
+    springs: Vec<Coordinate>,
+}
+
+/// Runs water from the springs one source at a time, yielding the cells each one changed.
+pub struct Flow<'a> {
+    grid: &'a mut Grid,
+    queue: VecDeque<Coordinate>,
+}
+
+/// A rectangle of a grid to draw, in absolute coordinates.
+pub struct View<'a> {
+    grid: &'a Grid,
+    xs: RangeInclusive<usize>,
+    ys: RangeInclusive<usize>,
+}
+
+/// A problem with the scan: a line that doesn't describe clay, or no clay at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    BadLine {
+        line: usize,
+        text: String,
+        reason: &'static str,
+    },
+    NoClay,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ScanError::BadLine { line, text, reason } => {
+                write!(f, "line {}: {} in {:?}", line, reason, text)
+            }
+            ScanError::NoClay => write!(f, "the scan has no clay in it"),
+        }
+    }
+}
+
+impl Error for ScanError {}
+
+/// Parses one coordinate of a vein, `x=N` or `x=A..B`, into an inclusive range.
+fn parse_range(start: &str, end: Option<&str>) -> Option<RangeInclusive<usize>> {
+    let start = start.parse().ok()?;
+    let end = end.map_or(Some(start), |end| end.parse().ok())?;
+    if end < start {
+        return None;
+    }
+    Some(start..=end)
+}
+
+/// Parses the scan. Each line gives a single value or a range for both `x` and `y`, in either
+/// order, so it can describe a point, a vein or a whole block of clay.
+#[aoc_generator(day17)]
+pub fn parse(input: &str) -> Result<Box<Grid>, ScanError> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new(r"^([xy])=(\d+)(?:\.\.(\d+))?,\s*([xy])=(\d+)(?:\.\.(\d+))?$").unwrap();
+    }
+    let mut blocks = vec![];
+    for (index, text) in input.lines().enumerate() {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let bad = |reason| ScanError::BadLine {
+            line: index + 1,
+            text: text.to_owned(),
+            reason,
+        };
+        let caps = RE
+            .captures(text)
+            .ok_or_else(|| bad("expected x=.., y=.."))?;
+        if caps[1] == caps[4] {
+            return Err(bad("both coordinates are on the same axis"));
+        }
+        let first = parse_range(&caps[2], caps.get(3).map(|m| m.as_str()))
+            .ok_or_else(|| bad("invalid range"))?;
+        let second = parse_range(&caps[5], caps.get(6).map(|m| m.as_str()))
+            .ok_or_else(|| bad("invalid range"))?;
+        blocks.push(if &caps[1] == "x" {
+            (first, second)
+        } else {
+            (second, first)
+        });
+    }
+    if blocks.is_empty() {
+        return Err(ScanError::NoClay);
+    }
+
+    let minx = blocks.iter().map(|(xs, _)| *xs.start()).min().unwrap();
+    let maxx = blocks.iter().map(|(xs, _)| *xs.end()).max().unwrap();
+    let miny = blocks.iter().map(|(_, ys)| *ys.start()).min().unwrap();
+    let maxy = blocks.iter().map(|(_, ys)| *ys.end()).max().unwrap();
+    // Leave a column of sand either side for water to fall down.
+    let (minx, maxx) = (minx.saturating_sub(1), maxx + 1);
+
+    let mut cells = vec![vec![Cell::Sand; maxx - minx + 1]; maxy - miny + 1];
+    for (xs, ys) in blocks {
+        for y in ys {
+            for x in xs.clone() {
+                cells[y - miny][x - minx] = Cell::Clay;
+            }
+        }
+    }
+    Ok(Box::new(Grid {
+        cells,
+        offsetx: minx,
+        offsety: miny,
+        scanned: miny..=maxy,
+        springs: vec![SPRING],
+    }))
+}
+
+impl Grid {
+    /// The cell at an absolute coordinate.
+    pub fn get(&self, c: Coordinate) -> Cell {
+        if c.x < self.offsetx || c.y < self.offsety {
+            return Cell::Sand;
+        }
+        self.cells
+            .get(c.y - self.offsety)
+            .and_then(|row| row.get(c.x - self.offsetx))
+            .cloned()
+            .unwrap_or(Cell::Sand)
+    }
+
+    fn bottom(&self) -> usize {
+        *self.scanned.end()
+    }
+
+    /// Grows the stored rectangle to take in `c`, and the columns either side of it.
+    fn extend_to(&mut self, c: Coordinate) {
+        let right = self.offsetx + self.cells.first().map_or(0, Vec::len);
+        // There's no column left of x=0 to take in.
+        let left = c.x.saturating_sub(1);
+        if left < self.offsetx {
+            let extra = self.offsetx - left;
+            for row in &mut self.cells {
+                row.splice(0..0, vec![Cell::Sand; extra]);
+            }
+            self.offsetx = left;
+        }
+        let width = cmp::max(right, c.x + 2) - self.offsetx;
+        for row in &mut self.cells {
+            row.resize(width, Cell::Sand);
+        }
+        if c.y < self.offsety {
+            let extra = self.offsety - c.y;
+            self.cells
+                .splice(0..0, vec![vec![Cell::Sand; width]; extra]);
+            self.offsety = c.y;
+        }
+    }
+
+    /// Starts water running from `springs`. It's followed down to `floor`, or else to the lowest
+    /// clay, and drains away below that.
+    pub fn flow(&mut self, springs: &[Coordinate], floor: Option<usize>) -> Flow<'_> {
+        for &spring in springs {
+            self.extend_to(spring);
+        }
+        if let Some(floor) = floor {
+            // A floor above the clay leaves no scanned rows, and only the rows above it to fall
+            // through.
+            self.scanned = *self.scanned.start()..=floor;
+            let width = self.cells.first().map_or(0, Vec::len);
+            self.cells.resize(
+                (floor + 1).saturating_sub(self.offsety),
+                vec![Cell::Sand; width],
+            );
+        }
+        self.springs = springs.to_vec();
+        let queue = springs
+            .iter()
+            .filter(|s| s.y <= self.bottom())
+            .map(|s| Coordinate::new(s.x - self.offsetx, s.y - self.offsety))
+            .collect();
+        Flow { grid: self, queue }
+    }
+
+    /// Counts the cells water reached, and the ones it settled in, among the scanned rows.
+    pub fn counts(&self) -> (u32, u32) {
+        let rows = self.scanned.clone().map(|y| &self.cells[y - self.offsety]);
+        rows.flatten()
+            .fold((0, 0), |(reached, settled), &cell| match cell {
+                Cell::WetSand => (reached + 1, settled),
+                Cell::Water => (reached + 1, settled + 1),
+                _ => (reached, settled),
+            })
+    }
+
+    /// A view of any rectangle of the grid, for display.
+    pub fn view(&self, xs: RangeInclusive<usize>, ys: RangeInclusive<usize>) -> View<'_> {
+        View { grid: self, xs, ys }
+    }
+}
+
+impl<'a> Flow<'a> {
+    /// Lets water fall from `source`, then fills up whatever basin it lands in, row by row, until
+    /// it spills over. Spills become new sources.
+    fn run_source(&mut self, source: Coordinate) -> Vec<(Coordinate, Cell)> {
+        let grid = &mut *self.grid;
+        let (offsetx, offsety) = (grid.offsetx, grid.offsety);
+        let mut changes = vec![];
+        let mut set = |cells: &mut Vec<Vec<Cell>>, x: usize, y: usize, cell: Cell| {
+            if cells[y][x] != cell {
+                cells[y][x] = cell;
+                changes.push((Coordinate::new(x + offsetx, y + offsety), cell));
+            }
+        };
+
+        // Make sand wet until we hit the bottom or non-sand.
+        let (x, mut y) = (source.x, source.y);
+        while y < grid.cells.len() && grid.cells[y][x] == Cell::Sand {
+            set(&mut grid.cells, x, y, Cell::WetSand);
+            y += 1;
+        }
+
+        // If we hit the bottom, we are done with this source- it leaks out.
+        // If we hit flowing water, we already know we can't fill here and are already done.
+        if y >= grid.cells.len() || grid.cells[y][x] == Cell::WetSand {
+            return changes;
+        }
+
+        let mut new_type = Cell::Water;
+        while new_type == Cell::Water && y > 0 {
+            y -= 1;
+
+            // If we hit clay in both directions without encountering a hole, make water and move up.
+            // If we find a hole, create wet sand and create a source at the hole.
+            let cells = &grid.cells;
+            let spread_water = |startx, direction: isize| {
+                let mut x = startx;
+                loop {
+                    // The grid keeps a column of sand either side of the clay, except at x=0.
+                    // Water running off the left of the world there drains away, like it does off
+                    // the bottom: it spills, but doesn't make a source.
+                    if x == 0 && direction < 0 {
+                        return (x, Some(None));
+                    }
+                    let nextx = (x as isize + direction) as usize;
+                    // First check to see if we hit a wall, in which case we know this direction is done.
+                    if cells[y][nextx] == Cell::Clay {
+                        return (x, None);
+                    }
+                    x = nextx;
+
+                    // Now check for a hole - if the tile under us is not our expected floor,
+                    // make a source.
+                    if cells[y + 1][x] != Cell::Water && cells[y + 1][x] != Cell::Clay {
+                        return (x, Some(Some(Coordinate { x, y: y + 1 })));
+                    }
+                }
+            };
+            let (minx, spill_left) = spread_water(source.x, -1);
+            let (maxx, spill_right) = spread_water(source.x, 1);
+
+            // If it spilled on either side, queue new sources and mark the row as running water.
+            for spill in [spill_left, spill_right].iter().flatten() {
+                self.queue.extend(spill);
+                new_type = Cell::WetSand;
+            }
+
+            // Fill in the row with the new water type.
+            for x in minx..=maxx {
+                set(&mut grid.cells, x, y, new_type);
+            }
+        }
+        changes
+    }
+}
+
+impl<'a> Iterator for Flow<'a> {
+    type Item = Vec<(Coordinate, Cell)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(source) = self.queue.pop_front() {
+            let changes = self.run_source(source);
+            if !changes.is_empty() {
+                return Some(changes);
+            }
+        }
+        None
+    }
+}
+
+fn run_water(grid: &mut Grid) -> (u32, u32) {
+    grid.flow(&[SPRING], None).for_each(drop);
+    grid.counts()
+}
+
+#[aoc(day17, part1)]
+fn solve_part1(grid: &Grid) -> u32 {
+    let mut grid = grid.clone();
+    run_water(&mut grid).0
+}
+
+#[aoc(day17, part2)]
+fn solve_part2(grid: &Grid) -> u32 {
+    let mut grid = grid.clone();
+    run_water(&mut grid).1
+}
+
+impl<'a> Display for View<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for y in self.ys.clone() {
+            for x in self.xs.clone() {
+                let c = Coordinate::new(x, y);
+                if self.grid.springs.contains(&c) {
+                    write!(f, "+")?;
+                    continue;
+                }
+                match self.grid.get(c) {
+                    Cell::Sand => write!(f, ".")?,
+                    Cell::Clay => write!(f, "#")?,
+                    Cell::WetSand => write!(f, "|")?,
+                    Cell::Water => write!(f, "~")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws the whole grid, from the highest spring down, like the puzzle does.
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let width = self.cells.first().map_or(0, Vec::len);
+        let top = self
+            .springs
+            .iter()
+            .map(|s| s.y)
+            .fold(self.offsety, cmp::min);
+        let view = self.view(self.offsetx..=self.offsetx + width - 1, top..=self.bottom());
+        write!(f, "{}", view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "
+x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504";
+
+    #[test]
+    fn test_parse() {
+        let grid = &parse(EXAMPLE).unwrap();
+        assert_eq!(grid.offsetx, 494);
+        assert_eq!(grid.offsety, 1);
+        assert_eq!(grid.cells.len(), 13);
+        assert_eq!(grid.cells[0].len(), 14);
+        use super::Cell::*;
+        assert_eq!(&grid.cells[1][1..6], &[Clay, Sand, Sand, Clay, Sand]);
+        assert_eq!(&grid.cells[6][1..6], &[Clay, Clay, Clay, Clay, Clay]);
+    }
+
+    #[test]
+    fn test_parse_shapes() {
+        use super::Cell::*;
+        let grid = parse("x=5, y=7\ny=9..10, x=3..4").unwrap();
+        assert_eq!((2, 7), (grid.offsetx, grid.offsety));
+        assert_eq!(
+            vec![
+                vec![Sand, Sand, Sand, Clay, Sand],
+                vec![Sand; 5],
+                vec![Sand, Clay, Clay, Sand, Sand],
+                vec![Sand, Clay, Clay, Sand, Sand],
+            ],
+            grid.cells
+        );
+
+        // Only vertical veins, or only horizontal ones, are fine too.
+        assert_eq!(3, parse("x=1, y=1..3").unwrap().cells.len());
+        assert_eq!(4, parse("y=1, x=0..2").unwrap().cells[0].len());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            ScanError::BadLine {
+                line: 3,
+                text: "x=495 y=2..7".to_owned(),
+                reason: "expected x=.., y=..",
+            },
+            parse("x=495, y=2..7\n\nx=495 y=2..7").unwrap_err()
+        );
+        assert_eq!(
+            "line 1: both coordinates are on the same axis in \"x=1, x=2\"",
+            parse("x=1, x=2").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 1: invalid range in \"x=1, y=5..3\"",
+            parse("x=1, y=5..3").unwrap_err().to_string()
+        );
+        assert_eq!(ScanError::NoClay, parse("\n").unwrap_err());
+    }
+
+    #[test]
+    fn test_flow() {
+        let mut grid = parse(EXAMPLE).unwrap();
+        let steps: Vec<_> = grid.flow(&[SPRING], None).collect();
+        assert_eq!(
+            vec![
+                (Coordinate::new(500, 0), Cell::WetSand),
+                (Coordinate::new(500, 1), Cell::WetSand)
+            ],
+            steps[0][..2]
+        );
+        assert!(steps[0].contains(&(Coordinate::new(500, 6), Cell::Water)));
+        assert_eq!(
+            "\
+......+.......
+......|.....#.
+.#..#||||...#.
+.#..#~~#|.....
+.#..#~~#|.....
+.#~~~~~#|.....
+.#~~~~~#|.....
+.#######|.....
+........|.....
+...|||||||||..
+...|#~~~~~#|..
+...|#~~~~~#|..
+...|#~~~~~#|..
+...|#######|..
+",
+            grid.to_string()
+        );
+        assert_eq!("~~#|\n~~#|\n", grid.view(499..=502, 5..=6).to_string());
+    }
+
+    #[test]
+    fn test_springs_and_floor() {
+        // A second spring over the right of the lower basin, outside the scanned columns.
+        let mut grid = parse(EXAMPLE).unwrap();
+        grid.flow(&[SPRING, Coordinate::new(509, 8)], None)
+            .for_each(drop);
+        assert_eq!((57 + 6, 29), grid.counts());
+
+        // Stopping at row 8 leaves out the lower basin altogether.
+        let mut grid = parse(EXAMPLE).unwrap();
+        grid.flow(&[SPRING], Some(8)).for_each(drop);
+        assert_eq!((25, 14), grid.counts());
+
+        // A spring sitting right on top of clay spills both ways.
+        let mut grid = parse(EXAMPLE).unwrap();
+        grid.flow(&[Coordinate::new(506, 0)], None).for_each(drop);
+        assert_eq!(Cell::WetSand, grid.get(Coordinate::new(505, 0)));
+        assert_eq!(Cell::WetSand, grid.get(Coordinate::new(507, 0)));
+
+        // A spring left of the scanned columns widens the grid without losing any on the right.
+        let mut grid = parse(EXAMPLE).unwrap();
+        grid.flow(&[SPRING, Coordinate::new(490, 0)], None)
+            .for_each(drop);
+        assert_eq!(Cell::Clay, grid.get(Coordinate::new(504, 10)));
+        assert_eq!(Cell::Clay, grid.get(Coordinate::new(506, 1)));
+        // Its water falls straight through the 13 scanned rows.
+        assert_eq!((57 + 13, 29), grid.counts());
+    }
+
+    #[test]
+    fn test_left_edge() {
+        // A basin with no wall on the left, so water runs off the edge of the world at x=0.
+        let scan = "y=5, x=0..4\nx=4, y=3..5";
+        for &spring in &[Coordinate::new(0, 0), Coordinate::new(2, 0)] {
+            let mut grid = parse(scan).unwrap();
+            grid.flow(&[spring], None).for_each(drop);
+            assert_eq!((5, 0), grid.counts());
+            assert_eq!("||||#.\n#####.\n", grid.view(0..=5, 4..=5).to_string());
+        }
+    }
+
+    #[test]
+    fn test_floor_above_clay() {
+        let mut grid = parse("y=5, x=0..4\nx=4, y=3..5").unwrap();
+        grid.flow(&[Coordinate::new(2, 0)], Some(1)).for_each(drop);
+        assert_eq!((0, 0), grid.counts());
+        assert_eq!("..+...\n..|...\n", grid.to_string());
+        assert_eq!(Cell::Sand, grid.get(Coordinate::new(2, 4)));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(57, solve_part1(&parse(EXAMPLE).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(29, solve_part2(&parse(EXAMPLE).unwrap()));
+    }
+}
@@ -0,0 +1,655 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+type Point = crate::coordinate::Coordinate<i32>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// The routes a regex matches, as a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    Step(Direction),
+    /// Each part in turn. The empty sequence is the empty option in a branch like `(N|)`.
+    Sequence(Vec<Route>),
+    /// Any one of the options.
+    Branch(Vec<Route>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// The byte offset in the input where parsing went wrong.
+    pub position: usize,
+    pub reason: &'static str,
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "position {}: {}", self.position, self.reason)
+    }
+}
+
+impl Error for SyntaxError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    /// The drawing isn't a rectangle with an odd number of rows and columns.
+    BadShape,
+    /// There should be exactly one `X`, not this many.
+    Origins(usize),
+    BadCharacter {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    /// A door without a room on both sides of it.
+    DanglingDoor { line: usize, column: usize },
+}
+
+impl Display for MapError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MapError::BadShape => write!(f, "the map isn't a grid of rooms and walls"),
+            MapError::Origins(n) => write!(f, "expected one `X` but found {}", n),
+            MapError::BadCharacter {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {}, column {}: unexpected {:?}",
+                line, column, found
+            ),
+            MapError::DanglingDoor { line, column } => {
+                write!(
+                    f,
+                    "line {}, column {}: door doesn't join two rooms",
+                    line, column
+                )
+            }
+        }
+    }
+}
+
+impl Error for MapError {}
+
+#[aoc_generator(day20)]
+pub fn parse(input: &str) -> Result<Route, SyntaxError> {
+    let start = input.len() - input.trim_start().len();
+    let mut parser = Parser {
+        input: input.trim_end().as_bytes(),
+        position: start,
+    };
+    parser.expect(b'^', "expected `^`")?;
+    let route = parser.sequence()?;
+    match parser.peek() {
+        Some(b'$') => parser.position += 1,
+        Some(b')') => return Err(parser.error("unmatched `)`")),
+        Some(b'|') => return Err(parser.error("`|` outside of a branch")),
+        _ => return Err(parser.error("expected `$`")),
+    }
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected input after `$`"));
+    }
+    Ok(route)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).cloned()
+    }
+
+    fn error(&self, reason: &'static str) -> SyntaxError {
+        SyntaxError {
+            position: self.position,
+            reason,
+        }
+    }
+
+    fn expect(&mut self, byte: u8, reason: &'static str) -> Result<(), SyntaxError> {
+        if self.peek() != Some(byte) {
+            return Err(self.error(reason));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Parses steps and branches up to whatever ends the sequence, which is left for the caller.
+    fn sequence(&mut self) -> Result<Route, SyntaxError> {
+        let mut parts = vec![];
+        loop {
+            match self.peek() {
+                Some(b'(') => {
+                    self.position += 1;
+                    let mut options = vec![self.sequence()?];
+                    while self.peek() == Some(b'|') {
+                        self.position += 1;
+                        options.push(self.sequence()?);
+                    }
+                    self.expect(b')', "expected `|` or `)`")?;
+                    parts.push(Route::Branch(options));
+                }
+                Some(b) => match Direction::from_byte(b) {
+                    Some(direction) => {
+                        self.position += 1;
+                        parts.push(Route::Step(direction));
+                    }
+                    None if b"|)$".contains(&b) => break,
+                    None => return Err(self.error("expected a direction")),
+                },
+                None => break,
+            }
+        }
+        Ok(Route::Sequence(parts))
+    }
+}
+
+impl Direction {
+    fn all() -> impl Iterator<Item = Direction> {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .iter()
+        .cloned()
+    }
+
+    fn from_byte(b: u8) -> Option<Direction> {
+        match b {
+            b'N' => Some(Direction::North),
+            b'E' => Some(Direction::East),
+            b'S' => Some(Direction::South),
+            b'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    fn offset(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    fn letter(self) -> char {
+        match self {
+            Direction::North => 'N',
+            Direction::East => 'E',
+            Direction::South => 'S',
+            Direction::West => 'W',
+        }
+    }
+}
+
+impl Route {
+    /// Follows the route from each of `from`, opening doors on the way, and returns where it can
+    /// end up.
+    fn walk(&self, from: BTreeSet<Point>, map: &mut Map) -> BTreeSet<Point> {
+        match self {
+            Route::Step(direction) => from
+                .into_iter()
+                .map(|room| {
+                    let next = room + direction.offset();
+                    map.link_rooms(room, next);
+                    next
+                })
+                .collect(),
+            Route::Sequence(parts) => parts.iter().fold(from, |ends, part| part.walk(ends, map)),
+            Route::Branch(options) => options
+                .iter()
+                .flat_map(|option| option.walk(from.clone(), map))
+                .collect(),
+        }
+    }
+}
+
+impl Display for Route {
+    /// Writes the route as a regex, without the `^` and `$` around it.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Route::Step(direction) => write!(f, "{}", direction.letter()),
+            Route::Sequence(parts) => parts.iter().try_for_each(|part| write!(f, "{}", part)),
+            Route::Branch(options) => {
+                write!(f, "(")?;
+                for (i, option) in options.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+                    write!(f, "{}", option)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Map(HashMap<Point, Room>);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Room {
+    neighbors: HashSet<Point>,
+}
+
+/// Opens every door along every route the regex matches, starting from the room at the origin.
+/// Whatever follows a branch carries on from the end of every option.
+pub fn build_map(route: &Route) -> Map {
+    let mut map = Map::new();
+    map.0.entry(Point::new(0, 0)).or_default();
+    route.walk(vec![Point::new(0, 0)].into_iter().collect(), &mut map);
+    map
+}
+
+fn build_distances(map: &Map) -> HashMap<Point, u32> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    let mut visited = HashSet::new();
+    queue.push_back((Point::new(0, 0), 0));
+    visited.insert(Point::new(0, 0));
+    while let Some((cur, distance)) = queue.pop_front() {
+        distances.insert(cur, distance);
+        for neighbor in map.neighbors(cur) {
+            if visited.insert(neighbor) {
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+impl Map {
+    fn new() -> Self {
+        Map(HashMap::new())
+    }
+
+    fn link_rooms(&mut self, a: Point, b: Point) {
+        self.0.entry(a).or_default().neighbors.insert(b);
+        self.0.entry(b).or_default().neighbors.insert(a);
+    }
+
+    fn neighbors<'a>(&'a self, room: Point) -> impl Iterator<Item = Point> + 'a {
+        self.0[&room].neighbors.iter().cloned()
+    }
+
+    /// A route that opens exactly the doors of the rooms reachable from the origin, going through
+    /// each door once: it branches along a shortest path tree, and steps through the remaining
+    /// doors as dead ends.
+    pub fn route(&self) -> Route {
+        let origin = Point::new(0, 0);
+        if !self.0.contains_key(&origin) {
+            return Route::Sequence(vec![]);
+        }
+        let mut parents = HashMap::new();
+        parents.insert(origin, origin);
+        let mut order = vec![];
+        let mut queue: VecDeque<_> = vec![origin].into_iter().collect();
+        while let Some(room) = queue.pop_front() {
+            order.push(room);
+            for direction in Direction::all() {
+                let next = room + direction.offset();
+                if self.0[&room].neighbors.contains(&next) && !parents.contains_key(&next) {
+                    parents.insert(next, room);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // Work back from the furthest rooms, so that each room's children are done before it.
+        // Routes are kept backwards until they branch, so a corridor only ever pushes one step
+        // onto the end of its route.
+        let mut backwards: HashMap<Point, Vec<Route>> = HashMap::new();
+        for &room in order.iter().rev() {
+            let mut options: Vec<Vec<Route>> = Direction::all()
+                .filter_map(|direction| {
+                    let next = room + direction.offset();
+                    if !self.0[&room].neighbors.contains(&next) || parents[&room] == next {
+                        None
+                    } else if parents[&next] == room {
+                        let mut parts = backwards.remove(&next).unwrap();
+                        parts.push(Route::Step(direction));
+                        Some(parts)
+                    } else if room < next {
+                        // A door off the tree, so the room beyond is reached some other way too.
+                        // Each of these is only stepped through from one side.
+                        Some(vec![Route::Step(direction)])
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            let parts = if options.len() > 1 {
+                let options = options.into_iter().map(|mut parts| {
+                    parts.reverse();
+                    Route::Sequence(parts)
+                });
+                vec![Route::Branch(options.collect())]
+            } else {
+                options.pop().unwrap_or_default()
+            };
+            backwards.insert(room, parts);
+        }
+        let mut parts = backwards.remove(&origin).unwrap();
+        parts.reverse();
+        Route::Sequence(parts)
+    }
+}
+
+#[aoc(day20, part1)]
+fn solve_part1(route: &Route) -> u32 {
+    let map = build_map(route);
+    *build_distances(&map).values().max().unwrap()
+}
+
+#[aoc(day20, part2)]
+fn solve_part2(route: &Route) -> usize {
+    let map = build_map(route);
+    build_distances(&map)
+        .values()
+        .filter(|&&v| v >= 1000)
+        .count()
+}
+
+impl Display for Map {
+    /// Draws the map the way the puzzle does, with `X` at the origin.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let minx = self.0.keys().map(|p| p.x).min().unwrap();
+        let miny = self.0.keys().map(|p| p.y).min().unwrap();
+        let maxx = self.0.keys().map(|p| p.x).max().unwrap();
+        let maxy = self.0.keys().map(|p| p.y).max().unwrap();
+        for y in miny..=maxy {
+            for x in minx..=maxx {
+                match self.0.get(&Point::new(x, y)) {
+                    None => write!(f, "##")?,
+                    Some(room) => {
+                        if room.neighbors.contains(&Point::new(x, y - 1)) {
+                            write!(f, "#-")?;
+                        } else {
+                            write!(f, "##")?;
+                        }
+                    }
+                }
+            }
+            writeln!(f, "#")?;
+            for x in minx..=maxx {
+                match self.0.get(&Point::new(x, y)) {
+                    None => write!(f, "##")?,
+                    Some(room) => {
+                        let r = if x == 0 && y == 0 { "X" } else { "." };
+                        if room.neighbors.contains(&Point::new(x - 1, y)) {
+                            write!(f, "|{}", r)?;
+                        } else {
+                            write!(f, "#{}", r)?;
+                        }
+                    }
+                }
+            }
+            writeln!(f, "#")?;
+        }
+        write!(f, "{}", "##".repeat((maxx - minx + 1).abs() as usize))?;
+        writeln!(f, "#")
+    }
+}
+
+impl FromStr for Map {
+    type Err = MapError;
+
+    /// Reads a map drawn the way the puzzle does. Rooms are `.`, or `X` at the origin, with `|`
+    /// and `-` for the doors between them and `#` for everything else.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&[u8]> = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::as_bytes)
+            .collect();
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.len() % 2 != 1 || width % 2 != 1 || rows.iter().any(|row| row.len() != width) {
+            return Err(MapError::BadShape);
+        }
+        let origins: Vec<_> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| b == b'X')
+                    .map(move |(c, _)| (r, c))
+            })
+            .collect();
+        let (oy, ox) = match origins[..] {
+            [origin] => origin,
+            _ => return Err(MapError::Origins(origins.len())),
+        };
+        let room =
+            |r: usize, c: usize| Point::new((c as i32 - ox as i32) / 2, (r as i32 - oy as i32) / 2);
+        let is_room = |r: usize, c: usize| {
+            let cell = rows.get(r).and_then(|row| row.get(c));
+            cell == Some(&b'.') || cell == Some(&b'X')
+        };
+
+        let mut map = Map::new();
+        for (r, row) in rows.iter().enumerate() {
+            for (c, &b) in row.iter().enumerate() {
+                let (line, column) = (r + 1, c + 1);
+                match (r % 2, c % 2, b) {
+                    (_, _, b'#') => {}
+                    (1, 1, b'.') | (1, 1, b'X') => {
+                        map.0.entry(room(r, c)).or_default();
+                    }
+                    (1, 0, b'|') | (0, 1, b'-') => {
+                        let (a, z) = if b == b'|' {
+                            ((r, c.wrapping_sub(1)), (r, c + 1))
+                        } else {
+                            ((r.wrapping_sub(1), c), (r + 1, c))
+                        };
+                        if !is_room(a.0, a.1) || !is_room(z.0, z.1) {
+                            return Err(MapError::DanglingDoor { line, column });
+                        }
+                        map.link_rooms(room(a.0, a.1), room(z.0, z.1));
+                    }
+                    _ => {
+                        return Err(MapError::BadCharacter {
+                            line,
+                            column,
+                            found: b as char,
+                        })
+                    }
+                }
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLES: &[(&str, u32)] = &[
+        ("^WNE$", 3),
+        ("^ENWWW(NEEE|SSE(EE|N))$", 10),
+        ("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$", 18),
+        ("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$", 23),
+        (
+            "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$",
+            31,
+        ),
+    ];
+
+    #[test]
+    fn test_parse() {
+        use Direction::*;
+        let step = Route::Step;
+        assert_eq!(
+            Ok(Route::Sequence(vec![
+                step(North),
+                Route::Branch(vec![
+                    Route::Sequence(vec![step(East), step(West)]),
+                    Route::Sequence(vec![]),
+                ]),
+                step(South),
+            ])),
+            parse(" ^N(EW|)S$\n")
+        );
+        for &(input, _) in EXAMPLES {
+            let route = parse(input).unwrap();
+            assert_eq!(input, format!("^{}$", route));
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let error = |input| parse(input).unwrap_err();
+        assert_eq!(0, error("NEWS$").position);
+        assert_eq!(3, error("^NE").position);
+        assert_eq!(3, error("^NEX$").position);
+        assert_eq!(4, error("^N(E$").position);
+        assert_eq!(3, error("^NE)$").position);
+        assert_eq!(2, error("^N|E$").position);
+        assert_eq!(4, error("^NE$W").position);
+        assert_eq!(
+            "position 6: expected `|` or `)`",
+            error("^N(E|W$").to_string()
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        for &(input, furthest) in EXAMPLES {
+            assert_eq!(furthest, solve_part1(&parse(input).unwrap()), "{}", input);
+        }
+    }
+
+    static MAPS: &[(&str, &str)] = &[
+        (
+            "^WNE$",
+            "
+#####
+#.|.#
+#-###
+#.|X#
+#####",
+        ),
+        (
+            "^ENWWW(NEEE|SSE(EE|N))$",
+            "
+#########
+#.|.|.|.#
+#-#######
+#.|.|.|.#
+#-#####-#
+#.#.#X|.#
+#-#-#####
+#.|.|.|.#
+#########",
+        ),
+        (
+            "^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$",
+            "
+###########
+#.|.#.|.#.#
+#-###-#-#-#
+#.|.|.#.#.#
+#-#####-#-#
+#.#.#X|.#.#
+#-#-#####-#
+#.#.|.|.|.#
+#-###-###-#
+#.|.|.#.|.#
+###########",
+        ),
+    ];
+
+    #[test]
+    fn test_draw_map() {
+        for &(input, drawing) in MAPS {
+            let map = build_map(&parse(input).unwrap());
+            assert_eq!(drawing.trim(), map.to_string().trim());
+            assert_eq!(Ok(map), drawing.parse());
+        }
+    }
+
+    #[test]
+    fn test_read_map() {
+        // A hand-drawn facility with a loop, and a room with no doors.
+        let map: Map = "
+            #######
+            #.|.#.#
+            #-#-###
+            #.|X#.#
+            #######"
+            .parse()
+            .unwrap();
+        let distances = build_distances(&map);
+        assert_eq!(4, distances.len());
+        assert_eq!(Some(&2), distances.get(&Point::new(-1, -1)));
+        assert_eq!(6, map.0.len());
+
+        let error = |drawing: &str| drawing.parse::<Map>().unwrap_err();
+        assert_eq!(MapError::BadShape, error("###\n#X#\n##"));
+        assert_eq!(MapError::BadShape, error("####\n#X.#\n####"));
+        assert_eq!(MapError::Origins(0), error("###\n#.#\n###"));
+        assert_eq!(MapError::Origins(2), error("#####\n#X|X#\n#####"));
+        assert_eq!(
+            MapError::DanglingDoor { line: 2, column: 5 },
+            error("#####\n#X#.|\n#####")
+        );
+        assert_eq!(
+            MapError::DanglingDoor { line: 1, column: 2 },
+            error("#-#\n#X#\n###")
+        );
+        assert_eq!(
+            MapError::BadCharacter {
+                line: 2,
+                column: 3,
+                found: '-'
+            },
+            error("#####\n#X-.#\n#####")
+        );
+        assert_eq!(
+            "line 2, column 5: door doesn't join two rooms",
+            error("#####\n#X#.|\n#####").to_string()
+        );
+    }
+
+    #[test]
+    fn test_route() {
+        for &(input, _) in EXAMPLES {
+            let map = build_map(&parse(input).unwrap());
+            let route = map.route();
+            assert_eq!(map, build_map(&route), "{}", route);
+            let doors: usize = map.0.values().map(|room| room.neighbors.len()).sum();
+            let steps = route
+                .to_string()
+                .chars()
+                .filter(char::is_ascii_uppercase)
+                .count();
+            assert_eq!(doors / 2, steps);
+        }
+        assert_eq!(
+            "WNE",
+            build_map(&parse("^WNE$").unwrap()).route().to_string()
+        );
+        // A loop of four rooms.
+        assert_eq!(
+            "(NES|E)",
+            build_map(&parse("^NESW$").unwrap()).route().to_string()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::elfcode::{self, Instruction, Operation, Parameter};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CPU {
+    registers: [usize; 6],
+    ip: usize,
+    ip_reg: usize,
+    program: Vec<Instruction>,
+    break_on_eqrr: bool,
+    seen: HashSet<usize>,
+    last_target: usize,
+}
+
+impl CPU {
+    pub fn tick(&mut self) -> Option<usize> {
+        if self.ip >= self.program.len() {
+            return Some(self.registers[0]);
+        }
+        self.registers[self.ip_reg] = self.ip;
+        let inst = self.program[self.ip];
+
+        // part 2 nonsense
+        if let Some(divisor_sum) = self.divisor_sum_pattern_match(inst) {
+            return Some(divisor_sum);
+        }
+
+        inst.execute(&mut self.registers);
+        self.ip = self.registers[self.ip_reg];
+        self.ip += 1;
+        None
+    }
+
+    fn divisor_sum_pattern_match(&mut self, instruction: Instruction) -> Option<usize> {
+        // There will only ever be 1 "eqrr" instruction - the larger register value will be the
+        // number to factor.
+        use self::{Operation::*, Parameter::*};
+        if let Instruction {
+            operation: Equals,
+            a: Register(a),
+            b: Register(b),
+            ..
+        } = instruction
+        {
+            let target = std::cmp::max(self.registers[a], self.registers[b]);
+            if !self.seen.insert(target) {
+                return Some(self.last_target);
+            }
+            self.last_target = target;
+            if !self.break_on_eqrr {
+                return None;
+            }
+            return Some(target);
+        }
+        None
+    }
+}
+
+#[aoc_generator(day21)]
+fn parse(input: &str) -> Box<CPU> {
+    let (ip_reg, program) = elfcode::parse_program(input).unwrap();
+    Box::new(CPU {
+        registers: [0; 6],
+        ip: 0,
+        ip_reg,
+        program,
+        break_on_eqrr: false,
+        seen: HashSet::new(),
+        last_target: 0,
+    })
+}
+
+#[aoc(day21, part1)]
+fn solve_part1(cpu: &CPU) -> usize {
+    let mut cpu = cpu.clone();
+    cpu.break_on_eqrr = true;
+    loop {
+        if let Some(val) = cpu.tick() {
+            return val;
+        }
+    }
+}
+
+#[aoc(day21, part2)]
+fn solve_part2(cpu: &CPU) -> usize {
+    // note: theres got to be a better way?
+    // this takes forever.
+    let mut cpu = cpu.clone();
+    cpu.break_on_eqrr = false;
+    loop {
+        if let Some(val) = cpu.tick() {
+            return val;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5";
+
+    #[test]
+    fn test_parse() {
+        use self::Operation::*;
+        use self::Parameter::*;
+        let cpu = parse(EXAMPLE);
+        assert_eq!(cpu.ip, 0);
+        assert_eq!(
+            &cpu.program[0],
+            &Instruction {
+                operation: Assign,
+                a: Value(5),
+                b: Value(0),
+                c: Register(1)
+            }
+        );
+        assert_eq!(cpu.program.len(), 7);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(6, solve_part1(&parse(EXAMPLE)));
+    }
+}
@@ -0,0 +1,382 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+
+pub type Coordinate = crate::coordinate::Coordinate<usize>;
+
+#[aoc_generator(day22)]
+fn parse(input: &str) -> Box<(usize, Coordinate)> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"depth: (\d+)\s+target: (\d+),(\d+)").unwrap();
+    }
+    let caps = RE.captures(input).unwrap();
+    let depth = caps[1].parse().unwrap();
+    let target = Coordinate::new(caps[2].parse().unwrap(), caps[3].parse().unwrap());
+    Box::new((depth, target))
+}
+
+#[aoc(day22, part1)]
+fn solve_part1(&(depth, target): &(usize, Coordinate)) -> usize {
+    Map::new(depth, target).risk(Coordinate::new(0, 0), target)
+}
+
+#[aoc(day22, part2)]
+fn solve_part2(&(depth, target): &(usize, Coordinate)) -> usize {
+    let map = Map::new(depth, target);
+    let mut queue: BinaryHeap<Reverse<Position>> = BinaryHeap::new();
+    let mut distances: HashMap<(Coordinate, Tool), usize> = HashMap::new();
+    let origin = Coordinate::new(0, 0);
+    queue.push(Reverse(Position {
+        coordinate: origin,
+        tool: Tool::Torch,
+        distance: 0,
+        heuristic: target.distance(origin),
+    }));
+    distances.insert((origin, Tool::Torch), 0);
+    while let Some(Reverse(position)) = queue.pop() {
+        if distances[&(position.coordinate, position.tool)] < position.distance {
+            continue;
+        }
+        if position.coordinate == target && position.tool == Tool::Torch {
+            // Done!
+            break;
+        }
+        map.visit_neighbors(
+            position.coordinate,
+            position.tool,
+            |coordinate, tool, cost| {
+                let mut enqueue_neighbor = false;
+                let distance = position.distance + cost;
+                match distances.entry((coordinate, tool)) {
+                    Entry::Occupied(mut e) => {
+                        if distance < *e.get() {
+                            *e.get_mut() = distance;
+                            enqueue_neighbor = true;
+                        }
+                    }
+                    Entry::Vacant(e) => {
+                        e.insert(distance);
+                        enqueue_neighbor = true;
+                    }
+                }
+                if enqueue_neighbor {
+                    queue.push(Reverse(Position {
+                        coordinate,
+                        tool,
+                        distance,
+                        heuristic: distance + coordinate.distance(target),
+                    }));
+                }
+            },
+        );
+    }
+    distances[&(target, Tool::Torch)]
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Position {
+    coordinate: Coordinate,
+    tool: Tool,
+    distance: usize,
+    heuristic: usize,
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.heuristic.cmp(&other.heuristic) {
+            Ordering::Equal => self.distance.cmp(&other.distance),
+            ord => ord,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Terrain {
+    Rocky,
+    Wet,
+    Narrow,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Tool {
+    Torch,
+    ClimbingGear,
+    Neither,
+}
+
+impl Terrain {
+    pub fn risk(self) -> usize {
+        match self {
+            Terrain::Rocky => 0,
+            Terrain::Wet => 1,
+            Terrain::Narrow => 2,
+        }
+    }
+
+    fn passable(self, tool: Tool) -> bool {
+        match self {
+            Terrain::Rocky => tool == Tool::Torch || tool == Tool::ClimbingGear,
+            Terrain::Wet => tool == Tool::ClimbingGear || tool == Tool::Neither,
+            Terrain::Narrow => tool == Tool::Torch || tool == Tool::Neither,
+        }
+    }
+}
+
+impl Tool {
+    fn other_tools(self) -> impl Iterator<Item = Self> {
+        [Tool::ClimbingGear, Tool::Torch, Tool::Neither]
+            .iter()
+            .cloned()
+            .filter(move |&t| t != self)
+    }
+}
+
+type Erosion = usize;
+impl From<Erosion> for Terrain {
+    fn from(from: Erosion) -> Self {
+        match from % 3 {
+            0 => Terrain::Rocky,
+            1 => Terrain::Wet,
+            2 => Terrain::Narrow,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The cave, extending as far right and down as anyone looks. Erosion levels are worked out on
+/// first use and kept, growing a rectangle of known cells from the mouth.
+#[derive(Debug)]
+pub struct Map {
+    depth: usize,
+    target: Coordinate,
+    erosion: RefCell<Vec<Vec<Erosion>>>,
+}
+
+impl Map {
+    pub fn new(depth: usize, target: Coordinate) -> Map {
+        Map {
+            depth,
+            target,
+            erosion: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn erosion(&self, coordinate: Coordinate) -> Erosion {
+        let mut erosion = self.erosion.borrow_mut();
+        if let Some(&level) = erosion
+            .get(coordinate.y)
+            .and_then(|row| row.get(coordinate.x))
+        {
+            return level;
+        }
+        // Grow by at least half again along an axis that's too short, so that searches creeping
+        // outwards a cell at a time don't refill every row each step.
+        let grow = |known: usize, wanted: usize| {
+            if wanted < known {
+                known
+            } else {
+                wanted.max(known + known / 2)
+            }
+        };
+        let width = grow(erosion.first().map_or(0, Vec::len), coordinate.x + 1);
+        let height = grow(erosion.len(), coordinate.y + 1);
+        erosion.resize_with(height, Vec::new);
+        // Each row only needs the one above it to be as wide, so fill them in from the top.
+        for y in 0..height {
+            for x in erosion[y].len()..width {
+                let geologic_index = if (x == 0 && y == 0) || Coordinate::new(x, y) == self.target {
+                    0
+                } else if y == 0 {
+                    x * 16807
+                } else if x == 0 {
+                    y * 48271
+                } else {
+                    erosion[y - 1][x] * erosion[y][x - 1]
+                };
+                erosion[y].push((geologic_index + self.depth) % 20183);
+            }
+        }
+        erosion[coordinate.y][coordinate.x]
+    }
+
+    pub fn terrain(&self, coordinate: Coordinate) -> Terrain {
+        Terrain::from(self.erosion(coordinate))
+    }
+
+    /// Every coordinate in the rectangle between two corners, inclusive, row by row.
+    fn region(top_left: Coordinate, bottom_right: Coordinate) -> impl Iterator<Item = Coordinate> {
+        (top_left.y..=bottom_right.y)
+            .flat_map(move |y| (top_left.x..=bottom_right.x).map(move |x| Coordinate::new(x, y)))
+    }
+
+    /// The total risk level of a rectangle, corners included.
+    pub fn risk(&self, top_left: Coordinate, bottom_right: Coordinate) -> usize {
+        Map::region(top_left, bottom_right)
+            .map(|c| self.terrain(c).risk())
+            .sum()
+    }
+
+    /// How many cells of each terrain there are in a rectangle, corners included.
+    pub fn terrain_counts(
+        &self,
+        top_left: Coordinate,
+        bottom_right: Coordinate,
+    ) -> HashMap<Terrain, usize> {
+        let mut counts = HashMap::new();
+        for c in Map::region(top_left, bottom_right) {
+            *counts.entry(self.terrain(c)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Draws a rectangle of the cave like the puzzle does: `M` for the mouth, `T` for the target,
+    /// and `.`, `=` and `|` for rocky, wet and narrow regions.
+    pub fn render(&self, top_left: Coordinate, bottom_right: Coordinate) -> String {
+        let mut out = String::new();
+        for c in Map::region(top_left, bottom_right) {
+            out.push(if c == Coordinate::new(0, 0) {
+                'M'
+            } else if c == self.target {
+                'T'
+            } else {
+                match self.terrain(c) {
+                    Terrain::Rocky => '.',
+                    Terrain::Wet => '=',
+                    Terrain::Narrow => '|',
+                }
+            });
+            if c.x == bottom_right.x {
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    fn visit_neighbors<F>(&self, coordinate: Coordinate, tool: Tool, mut f: F)
+    where
+        F: FnMut(Coordinate, Tool, usize),
+    {
+        // Visit all neighbors with the same tool.
+        [
+            coordinate.up(),
+            coordinate.left(),
+            coordinate.right(),
+            coordinate.down(),
+        ]
+        .iter()
+        .cloned()
+        .filter_map(|c| c)
+        .filter(|&c| self.terrain(c).passable(tool))
+        .for_each(|c| f(c, tool, 1));
+
+        // Visit other tools for the same coordinate.
+        tool.other_tools()
+            .filter(|&t| self.terrain(coordinate).passable(t))
+            .for_each(|t| f(coordinate, t, 7));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erosion() {
+        let map = Map::new(510, Coordinate::new(10, 10));
+        assert_eq!(510, map.erosion(Coordinate::new(0, 0)));
+        assert_eq!(17317, map.erosion(Coordinate::new(1, 0)));
+        assert_eq!(8415, map.erosion(Coordinate::new(0, 1)));
+        assert_eq!(1805, map.erosion(Coordinate::new(1, 1)));
+        assert_eq!(510, map.erosion(Coordinate::new(10, 10)));
+        // Far past where the old fixed-size map stopped.
+        assert_eq!(Terrain::Narrow, map.terrain(Coordinate::new(100, 3)));
+        let size = |map: &Map| {
+            let erosion = map.erosion.borrow();
+            (erosion.len(), erosion.iter().map(Vec::len).min().unwrap())
+        };
+        let (height, width) = size(&map);
+        assert!(height >= 11 && width >= 101);
+        // Cells already worked out are looked up, not grown into.
+        assert_eq!(510, map.erosion(Coordinate::new(10, 10)));
+        assert_eq!((height, width), size(&map));
+    }
+
+    #[test]
+    fn test_target_on_edge() {
+        // The target's erosion level overrides the edge formula.
+        let map = Map::new(510, Coordinate::new(0, 3));
+        assert_eq!(510, map.erosion(Coordinate::new(0, 3)));
+        assert_eq!(
+            (4 * 48271 + 510) % 20183,
+            map.erosion(Coordinate::new(0, 4))
+        );
+        assert_eq!(0, solve_part1(&(510, Coordinate::new(0, 3))));
+        assert_eq!(1, solve_part1(&(510, Coordinate::new(3, 0))));
+        assert_eq!(0, solve_part2(&(510, Coordinate::new(0, 0))));
+        assert_eq!(17, solve_part2(&(510, Coordinate::new(3, 0))));
+        assert_eq!(3, solve_part2(&(510, Coordinate::new(0, 3))));
+    }
+
+    #[test]
+    fn test_render() {
+        let map = Map::new(510, Coordinate::new(10, 10));
+        assert_eq!(
+            "\
+M=.|=.|.|=.|=|=.
+.|=|=|||..|.=...
+.==|....||=..|==
+=.|....|.==.|==.
+=|..==...=.|==..
+=||.=.=||=|=..|=
+|.=.===|||..=..|
+|..==||=.|==|===
+.=..===..=|.|||.
+.======|||=|=.|=
+.===|=|===T===||
+=|||...|==..|=.|
+=.=|=.=..=.||==|
+||=|=...|==.=|==
+|=.=||===.|||===
+||.|==.|.|.||=||
+",
+            map.render(Coordinate::new(0, 0), Coordinate::new(15, 15))
+        );
+        assert_eq!(
+            "||=\n==T\n",
+            map.render(Coordinate::new(8, 9), Coordinate::new(10, 10))
+        );
+    }
+
+    #[test]
+    fn test_region_stats() {
+        let map = Map::new(510, Coordinate::new(10, 10));
+        let (origin, target) = (Coordinate::new(0, 0), Coordinate::new(10, 10));
+        assert_eq!(114, map.risk(origin, target));
+        assert_eq!(
+            map.risk(origin, target),
+            map.risk(origin, Coordinate::new(10, 4)) + map.risk(Coordinate::new(0, 5), target)
+        );
+        let counts = map.terrain_counts(origin, target);
+        assert_eq!(121, counts.values().sum::<usize>());
+        assert_eq!(114, counts[&Terrain::Wet] + 2 * counts[&Terrain::Narrow]);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(114, solve_part1(&(510, Coordinate::new(10, 10))));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(45, solve_part2(&(510, Coordinate::new(10, 10))));
+    }
+}
@@ -0,0 +1,759 @@
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::ops::{Add, Div, Mul};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Bot {
+    pub pos: Position,
+    pub strength: i64,
+}
+
+/// Every integer position between two corners, inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid(pub Position, pub Position);
+
+/// The positions in range of the most bots that are closest to the origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Best {
+    /// How many bots are in range of each of the points.
+    pub count: usize,
+    pub distance: i64,
+    /// In ascending order.
+    pub points: Vec<Position>,
+}
+
+static ORIGIN: Position = Position { x: 0, y: 0, z: 0 };
+
+#[aoc_generator(day23)]
+pub fn parse(input: &str) -> Vec<Bot> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"pos=<(-?\d+),(-?\d+),(-?\d+)>, r=(\d+)").unwrap();
+    }
+    RE.captures_iter(input)
+        .filter_map(|caps| {
+            Some(Bot {
+                pos: Position {
+                    x: caps[1].parse().ok()?,
+                    y: caps[2].parse().ok()?,
+                    z: caps[3].parse().ok()?,
+                },
+                strength: caps[4].parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[aoc(day23, part1)]
+fn solve_part1(bots: &[Bot]) -> usize {
+    let strongest = bots.iter().max_by_key(|bot| bot.strength).unwrap();
+    bots.iter()
+        .filter(|&bot| bot.pos.distance(strongest.pos) <= strongest.strength)
+        .count()
+}
+
+#[aoc(day23, part2)]
+fn solve_part2(bots: &[Bot]) -> i64 {
+    best_points(bots, 1).distance
+}
+
+/// Finds the points in range of the most bots at the least distance from the origin, by branch
+/// and bound over boxes. Small, promising boxes are tried first so that a good point turns up
+/// early, and boxes that can't hold anything as good as the best point so far are dropped. Whole
+/// faces of points can tie, so at most `limit` of them are listed; the count and distance are
+/// exact either way.
+pub fn best_points(bots: &[Bot], limit: usize) -> Best {
+    let bound = |c: Cuboid| (c.num_bots_intersecting(bots), Reverse(c));
+    let mut queue = BinaryHeap::new();
+    queue.push(bound(Cuboid::from(bots)));
+    let mut best = Best {
+        count: 0,
+        distance: i64::MAX,
+        points: vec![],
+    };
+    while let Some((count, Reverse(cuboid))) = queue.pop() {
+        let distance = cuboid.distance_to_origin();
+        if cuboid.0 == cuboid.1 {
+            match (count, Reverse(distance)).cmp(&(best.count, Reverse(best.distance))) {
+                Ordering::Greater => {
+                    best = Best {
+                        count,
+                        distance,
+                        points: vec![cuboid.0],
+                    }
+                }
+                Ordering::Equal if best.points.len() < limit => best.points.push(cuboid.0),
+                _ => {}
+            }
+            continue;
+        }
+        // A box is worth splitting if it might hold a point in range of more bots than the best,
+        // or of as many but closer (or as close, while there's room for ties). Only bots that
+        // reach that close to the origin count towards the second. This is what stops the search
+        // from splitting up whole faces of points that are just as good.
+        let radius = if best.points.len() < limit {
+            best.distance
+        } else {
+            best.distance - 1
+        };
+        let near = || {
+            bots.iter()
+                .filter(|&&bot| bot.pos.distance(ORIGIN) - bot.strength <= radius)
+                .filter(|&&bot| cuboid.intersects(bot))
+                .count()
+        };
+        if count > best.count || (distance <= radius && near() >= best.count) {
+            queue.extend(cuboid.subdivide().map(bound));
+        }
+    }
+    best.points.sort();
+    best
+}
+
+/// Finds the same thing as `best_points` by trying every point in `cuboid`. Only practical for
+/// small boxes, but it has nothing to get wrong.
+pub fn scan_points(bots: &[Bot], cuboid: Cuboid) -> Best {
+    let (a, b) = (cuboid.0, cuboid.1);
+    let mut points: Vec<_> = (a.x..=b.x)
+        .into_par_iter()
+        .flat_map_iter(|x| {
+            (a.y..=b.y).flat_map(move |y| (a.z..=b.z).map(move |z| Position::new(x, y, z)))
+        })
+        .map(|p| {
+            let count = bots.iter().filter(|bot| bot.contains(p)).count();
+            (count, Reverse(p.distance(ORIGIN)), p)
+        })
+        .collect();
+    let &(count, Reverse(distance), _) = points.iter().max_by_key(|(c, d, _)| (c, d)).unwrap();
+    points.retain(|&(c, Reverse(d), _)| c == count && d == distance);
+    let mut points: Vec<_> = points.into_iter().map(|(_, _, p)| p).collect();
+    points.sort();
+    Best {
+        count,
+        distance,
+        points,
+    }
+}
+
+/// Finds the same count and distance as `best_points` another way, by sweeping intervals. A
+/// position's distance from a bot is the biggest of the distances along the four diagonals
+/// `x+y+z`, `x+y-z`, `x-y+z` and `-x+y+z`, so each bot's range is an interval on each diagonal.
+/// The last three diagonals are cut wherever an interval starts or stops, and in each cell a sweep
+/// along the first finds where the most of the bots active there overlap. The distance from the
+/// origin is the biggest of the four diagonals too, which the cell and interval bound it by. This
+/// takes time in proportion to at least the cube of the number of bots, so it's for checking.
+pub fn distance_sweep(bots: &[Bot]) -> (usize, i64) {
+    let intervals: Vec<[(i64, i64); 4]> = bots
+        .iter()
+        .map(|bot| {
+            let (x, y, z, r) = (bot.pos.x, bot.pos.y, bot.pos.z, bot.strength);
+            let mut spans = [(0, 0); 4];
+            for (span, &d) in spans
+                .iter_mut()
+                .zip(&[x + y + z, x + y - z, x - y + z, y + z - x])
+            {
+                *span = (d - r, d + r);
+            }
+            spans
+        })
+        .collect();
+    // The cells along each diagonal: runs of values where no interval starts or stops.
+    let cells = |axis: usize| {
+        let mut cuts: Vec<_> = intervals
+            .iter()
+            .flat_map(|spans| vec![spans[axis].0, spans[axis].1 + 1])
+            .collect();
+        cuts.sort();
+        cuts.dedup();
+        cuts.windows(2)
+            .map(|w| (w[0], w[1] - 1))
+            .collect::<Vec<_>>()
+    };
+    let (cells2, cells3, cells4) = (cells(1), cells(2), cells(3));
+    let mut best = (0, Reverse(i64::MAX));
+    for &c2 in &cells2 {
+        for &c3 in &cells3 {
+            for &c4 in &cells4 {
+                let cell = [c2, c3, c4];
+                let active: Vec<_> = intervals
+                    .iter()
+                    .filter(|spans| {
+                        (0..3).all(|i| spans[i + 1].0 <= cell[i].0 && cell[i].1 <= spans[i + 1].1)
+                    })
+                    .map(|spans| spans[0])
+                    .collect();
+                if active.len() < best.0 {
+                    continue;
+                }
+                let mut cuts: Vec<_> = active.iter().flat_map(|&(a, b)| vec![a, b + 1]).collect();
+                cuts.sort();
+                cuts.dedup();
+                for w in cuts.windows(2) {
+                    let span = (w[0], w[1] - 1);
+                    let count = active
+                        .iter()
+                        .filter(|&&(a, b)| a <= span.0 && span.1 <= b)
+                        .count();
+                    if count < best.0 {
+                        continue;
+                    }
+                    if let Some(distance) = nearest(cell, span) {
+                        best = best.max((count, Reverse(distance)));
+                    }
+                }
+            }
+        }
+    }
+    (best.0, (best.1).0)
+}
+
+/// The least distance from the origin of a position whose last three diagonals are in `cell` and
+/// whose first is in `span`, if there is one.
+fn nearest(cell: [(i64, i64); 3], span: (i64, i64)) -> Option<i64> {
+    // The last three diagonals add up to the first, and they all have the same parity, or else
+    // halving their sums in pairs wouldn't give whole coordinates.
+    let within = |distance: i64, parity: i64| {
+        let of_parity = |(a, b): (i64, i64)| {
+            let a = std::cmp::max(a, -distance);
+            let b = std::cmp::min(b, distance);
+            let a = a + (a - parity).rem_euclid(2);
+            let b = b - (b - parity).rem_euclid(2);
+            if a <= b {
+                Some((a, b))
+            } else {
+                None
+            }
+        };
+        let mut sum = (0, 0);
+        for &c in &cell {
+            let (a, b) = of_parity(c)?;
+            sum = (sum.0 + a, sum.1 + b);
+        }
+        let (a, b) = (std::cmp::max(sum.0, span.0), std::cmp::min(sum.1, span.1));
+        of_parity((a, b)).map(|_| ())
+    };
+    let reach = (0..3)
+        .map(|i| std::cmp::max(cell[i].0.abs(), cell[i].1.abs()))
+        .chain(vec![span.0.abs(), span.1.abs()])
+        .max()
+        .unwrap();
+    (0..2)
+        .filter(|&parity| within(reach, parity).is_some())
+        .map(|parity| {
+            // Whether some position is within a distance only gets more likely as it grows.
+            let (mut low, mut high) = (0, reach);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if within(mid, parity).is_some() {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            low
+        })
+        .min()
+}
+
+/// How many of the bots are in range of `p`.
+pub fn coverage(bots: &[Bot], p: Position) -> usize {
+    bots.iter().filter(|bot| bot.contains(p)).count()
+}
+
+/// The largest set of bots whose ranges all overlap each other, as ascending indices into `bots`.
+/// Every bot in range of a point overlaps every other one, so this is at least as big as
+/// `best_points`' count; ranges can overlap pairwise without sharing a point, though, so it can
+/// be bigger.
+pub fn max_clique(bots: &[Bot]) -> Vec<usize> {
+    let overlaps: Vec<Vec<bool>> = bots
+        .iter()
+        .map(|a| bots.iter().map(|&b| a.overlaps(b)).collect())
+        .collect();
+    let mut best = vec![];
+    expand_clique(
+        &overlaps,
+        &mut vec![],
+        (0..bots.len()).collect(),
+        vec![],
+        &mut best,
+    );
+    best.sort();
+    best
+}
+
+/// Bron-Kerbosch with pivoting: grows `clique` with each of `candidates` in turn, skipping
+/// the ones already tried (`excluded`), and remembers the biggest clique found.
+fn expand_clique(
+    overlaps: &[Vec<bool>],
+    clique: &mut Vec<usize>,
+    mut candidates: Vec<usize>,
+    mut excluded: Vec<usize>,
+    best: &mut Vec<usize>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() && clique.len() > best.len() {
+            *best = clique.clone();
+        }
+        return;
+    }
+    if clique.len() + candidates.len() <= best.len() {
+        return;
+    }
+    // Any clique that could be grown includes either the pivot or something it doesn't overlap.
+    let pivot = candidates
+        .iter()
+        .chain(&excluded)
+        .copied()
+        .max_by_key(|&p| candidates.iter().filter(|&&c| overlaps[p][c]).count())
+        .unwrap();
+    let tries: Vec<_> = candidates
+        .iter()
+        .copied()
+        .filter(|&c| c == pivot || !overlaps[pivot][c])
+        .collect();
+    for v in tries {
+        let neighbours = |set: &[usize]| {
+            set.iter()
+                .copied()
+                .filter(|&u| u != v && overlaps[v][u])
+                .collect()
+        };
+        clique.push(v);
+        expand_clique(
+            overlaps,
+            clique,
+            neighbours(&candidates),
+            neighbours(&excluded),
+            best,
+        );
+        clique.pop();
+        candidates.retain(|&u| u != v);
+        excluded.push(v);
+    }
+}
+
+impl<'a, T: IntoIterator<Item = &'a Bot>> From<T> for Cuboid {
+    /// The smallest box holding everything in range of any of the bots.
+    fn from(f: T) -> Cuboid {
+        let mut bots = f.into_iter();
+        let first = bots.next().expect("no bots");
+        let reach = |bot: &Bot| {
+            let r = Position::new(bot.strength, bot.strength, bot.strength);
+            Cuboid(bot.pos + r * -1, bot.pos + r)
+        };
+        bots.fold(reach(first), |cuboid, bot| {
+            let other = reach(bot);
+            Cuboid(
+                Position::new(
+                    cuboid.0.x.min(other.0.x),
+                    cuboid.0.y.min(other.0.y),
+                    cuboid.0.z.min(other.0.z),
+                ),
+                Position::new(
+                    cuboid.1.x.max(other.1.x),
+                    cuboid.1.y.max(other.1.y),
+                    cuboid.1.z.max(other.1.z),
+                ),
+            )
+        })
+    }
+}
+
+impl Position {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Position { x, y, z }
+    }
+
+    fn distance(&self, other: Position) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl Cuboid {
+    /// Splits the box in half along every axis that's more than one position wide. The parts
+    /// don't overlap, and between them hold every position of the box.
+    fn subdivide(self) -> impl Iterator<Item = Cuboid> {
+        let halves = |a: i64, b: i64| {
+            let mid = a + (b - a).div_euclid(2);
+            if a == b {
+                vec![(a, b)]
+            } else {
+                vec![(a, mid), (mid + 1, b)]
+            }
+        };
+        let (a, b) = (self.0, self.1);
+        let mut parts = vec![];
+        for &(x0, x1) in &halves(a.x, b.x) {
+            for &(y0, y1) in &halves(a.y, b.y) {
+                for &(z0, z1) in &halves(a.z, b.z) {
+                    parts.push(Cuboid(Position::new(x0, y0, z0), Position::new(x1, y1, z1)));
+                }
+            }
+        }
+        parts.into_iter()
+    }
+
+    fn num_bots_intersecting(self, bots: &[Bot]) -> usize {
+        bots.iter().filter(|&&bot| self.intersects(bot)).count()
+    }
+
+    /// Every position in the box that's in range of exactly `k` of the bots, in ascending order.
+    pub fn covered_by_exactly(self, bots: &[Bot], k: usize) -> Vec<Position> {
+        let touching: Vec<_> = bots
+            .iter()
+            .copied()
+            .filter(|&bot| self.intersects(bot))
+            .collect();
+        let inside = touching.iter().filter(|&&bot| self.inside(bot)).count();
+        let mut points = if touching.len() < k || inside > k {
+            vec![]
+        } else if touching.len() == inside || self.0 == self.1 {
+            // Every bot touching the box holds all of it, so every position has the same count.
+            if inside == k {
+                self.points().collect()
+            } else {
+                vec![]
+            }
+        } else {
+            self.subdivide()
+                .flat_map(|part| part.covered_by_exactly(&touching, k))
+                .collect()
+        };
+        points.sort();
+        points
+    }
+
+    /// Every position in the box.
+    pub fn points(self) -> impl Iterator<Item = Position> {
+        let (a, b) = (self.0, self.1);
+        (a.x..=b.x).flat_map(move |x| {
+            (a.y..=b.y).flat_map(move |y| (a.z..=b.z).map(move |z| Position::new(x, y, z)))
+        })
+    }
+
+    /// Whether the whole box is in range of the bot. Ranges are convex, so the corners will do.
+    pub fn inside(self, bot: Bot) -> bool {
+        let (a, b) = (self.0, self.1);
+        [a.x, b.x].iter().all(|&x| {
+            [a.y, b.y].iter().all(|&y| {
+                [a.z, b.z]
+                    .iter()
+                    .all(|&z| bot.contains(Position::new(x, y, z)))
+            })
+        })
+    }
+
+    pub fn intersects(self, bot: Bot) -> bool {
+        let projected = Position {
+            x: clamp(bot.pos.x, self.0.x, self.1.x),
+            y: clamp(bot.pos.y, self.0.y, self.1.y),
+            z: clamp(bot.pos.z, self.0.z, self.1.z),
+        };
+        bot.contains(projected)
+    }
+
+    /// The distance from the origin to the nearest position in the box.
+    fn distance_to_origin(self) -> i64 {
+        let projected = Position {
+            x: clamp(0, self.0.x, self.1.x),
+            y: clamp(0, self.0.y, self.1.y),
+            z: clamp(0, self.0.z, self.1.z),
+        };
+        projected.distance(ORIGIN)
+    }
+}
+
+impl Bot {
+    pub fn contains(self, p: Position) -> bool {
+        p.distance(self.pos) <= self.strength
+    }
+
+    /// Whether some position is in range of both bots.
+    pub fn overlaps(self, other: Bot) -> bool {
+        self.pos.distance(other.pos) <= self.strength + other.strength
+    }
+
+    /// How many positions are in range of both bots. Each slice across x is where two diamonds
+    /// meet, and turning those 45 degrees makes them squares, so this takes time in proportion to
+    /// how wide the overlap is.
+    pub fn overlap_volume(self, other: Bot) -> u128 {
+        let (a, b) = (self, other);
+        let xs = std::cmp::max(a.pos.x - a.strength, b.pos.x - b.strength)
+            ..=std::cmp::min(a.pos.x + a.strength, b.pos.x + b.strength);
+        xs.map(|x| {
+            // Within the slice, each range is |y - y0| + |z - z0| <= r, which is a square in
+            // u = y + z and v = y - z, where u and v have the same parity.
+            let (ra, rb) = (
+                a.strength - (x - a.pos.x).abs(),
+                b.strength - (x - b.pos.x).abs(),
+            );
+            let (ua, va) = (a.pos.y + a.pos.z, a.pos.y - a.pos.z);
+            let (ub, vb) = (b.pos.y + b.pos.z, b.pos.y - b.pos.z);
+            let (u_even, u_odd) = parities(
+                std::cmp::max(ua - ra, ub - rb),
+                std::cmp::min(ua + ra, ub + rb),
+            );
+            let (v_even, v_odd) = parities(
+                std::cmp::max(va - ra, vb - rb),
+                std::cmp::min(va + ra, vb + rb),
+            );
+            u_even * v_even + u_odd * v_odd
+        })
+        .sum()
+    }
+}
+
+/// How many even and odd numbers there are from `a` to `b` inclusive.
+fn parities(a: i64, b: i64) -> (u128, u128) {
+    if a > b {
+        return (0, 0);
+    }
+    let evens = b.div_euclid(2) - (a - 1).div_euclid(2);
+    (evens as u128, (b - a + 1 - evens) as u128)
+}
+
+fn clamp(p: i64, a: i64, b: i64) -> i64 {
+    use std::cmp::{max, min};
+    let (a, b) = (min(a, b), max(a, b));
+    if p >= a && p <= b {
+        p
+    } else if p < a {
+        a
+    } else {
+        b
+    }
+}
+
+impl Ord for Cuboid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.0.distance(self.1).cmp(&other.0.distance(other.1)) {
+            Ordering::Equal => self.distance_to_origin().cmp(&other.distance_to_origin()),
+            x => x,
+        }
+    }
+}
+
+impl PartialOrd for Cuboid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Position {
+    type Output = Self;
+
+    fn add(self, other: Position) -> Position {
+        Position {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Mul<i64> for Position {
+    type Output = Self;
+
+    fn mul(self, scalar: i64) -> Position {
+        Position {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+}
+
+impl Div<i64> for Position {
+    type Output = Self;
+
+    fn div(self, scalar: i64) -> Position {
+        Position {
+            x: self.x / scalar,
+            y: self.y / scalar,
+            z: self.z / scalar,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE1: &str = "
+pos=<0,0,0>, r=4
+pos=<1,0,0>, r=1
+pos=<4,0,0>, r=3
+pos=<0,2,0>, r=1
+pos=<0,5,0>, r=3
+pos=<0,0,3>, r=1
+pos=<1,1,1>, r=1
+pos=<1,1,2>, r=1
+pos=<1,3,1>, r=1";
+
+    static EXAMPLE2: &str = "
+pos=<10,12,12>, r=2
+pos=<12,14,12>, r=2
+pos=<16,12,12>, r=4
+pos=<14,14,14>, r=6
+pos=<50,50,50>, r=200
+pos=<10,10,10>, r=5";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(7, solve_part1(&parse(EXAMPLE1)));
+    }
+
+    /// Small random swarms, from a fixed seed.
+    fn random_bots(seed: u64, n: usize) -> Vec<Bot> {
+        let mut state = seed;
+        let mut next = |range: i64| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % range as u64) as i64
+        };
+        (0..n)
+            .map(|_| Bot {
+                pos: Position::new(next(13) - 6, next(13) - 6, next(13) - 6),
+                strength: next(5),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_best_points() {
+        assert_eq!(
+            Best {
+                count: 5,
+                distance: 36,
+                points: vec![Position::new(12, 12, 12)],
+            },
+            best_points(&parse(EXAMPLE2), 10)
+        );
+        for seed in 0..20 {
+            let bots = random_bots(seed, 8);
+            let best = best_points(&bots, usize::MAX);
+            assert_eq!(
+                scan_points(&bots, Cuboid::from(&bots)),
+                best,
+                "seed {}",
+                seed
+            );
+            assert_eq!(
+                (best.count, best.distance),
+                distance_sweep(&bots),
+                "seed {}",
+                seed
+            );
+        }
+        // Spread out too far to scan, with ranges that don't line up on a grid.
+        for seed in 0..20 {
+            let bots: Vec<_> = random_bots(seed, 12)
+                .into_iter()
+                .map(|bot| Bot {
+                    pos: bot.pos * 101,
+                    strength: bot.strength * 151 + seed as i64,
+                })
+                .collect();
+            let best = best_points(&bots, 1);
+            assert_eq!(
+                (best.count, best.distance),
+                distance_sweep(&bots),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_subdivide() {
+        let cuboid = Cuboid(Position::new(-1, 0, 5), Position::new(1, 0, 6));
+        let parts: Vec<_> = cuboid.subdivide().collect();
+        assert_eq!(4, parts.len());
+        let volume = |c: &Cuboid| (c.1.x - c.0.x + 1) * (c.1.y - c.0.y + 1) * (c.1.z - c.0.z + 1);
+        assert_eq!(volume(&cuboid), parts.iter().map(volume).sum::<i64>());
+        let cuboid = Cuboid(Position::new(-3, -2, 1), Position::new(4, 5, 9));
+        assert_eq!(1, cuboid.distance_to_origin());
+    }
+
+    #[test]
+    fn test_distance_sweep() {
+        assert_eq!((5, 36), distance_sweep(&parse(EXAMPLE2)));
+    }
+
+    #[test]
+    fn test_coverage() {
+        let bots = parse(EXAMPLE2);
+        assert_eq!(5, coverage(&bots, Position::new(12, 12, 12)));
+        assert_eq!(vec![0, 1, 2, 3, 4], max_clique(&bots));
+        for seed in 0..20 {
+            let bots = random_bots(seed, 8);
+            let best = best_points(&bots, 1);
+            assert_eq!(best.count, coverage(&bots, best.points[0]));
+            // The biggest subset of bots that all overlap, by trying all of them.
+            let biggest = (0..1 << bots.len())
+                .filter(|&set: &u32| {
+                    (0..bots.len()).all(|i| {
+                        (0..bots.len()).all(|j| {
+                            set & (1 << i) == 0 || set & (1 << j) == 0 || bots[i].overlaps(bots[j])
+                        })
+                    })
+                })
+                .map(u32::count_ones)
+                .max();
+            let clique = max_clique(&bots);
+            assert_eq!(biggest, Some(clique.len() as u32), "seed {}", seed);
+            assert!(clique.len() >= best.count);
+        }
+    }
+
+    #[test]
+    fn test_overlap_volume() {
+        let bot = |x, y, z, strength| Bot {
+            pos: Position::new(x, y, z),
+            strength,
+        };
+        assert_eq!(25, bot(0, 0, 0, 2).overlap_volume(bot(0, 0, 0, 2)));
+        assert_eq!(0, bot(0, 0, 0, 1).overlap_volume(bot(3, 0, 0, 1)));
+        assert_eq!(1, bot(0, 0, 0, 1).overlap_volume(bot(2, 0, 0, 1)));
+        let bots = random_bots(7, 20);
+        for &a in &bots {
+            for &b in &bots {
+                let both = Cuboid::from(&[a, b])
+                    .points()
+                    .filter(|&p| a.contains(p) && b.contains(p))
+                    .count();
+                assert_eq!(both as u128, a.overlap_volume(b), "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_covered_by_exactly() {
+        for seed in 0..10 {
+            let bots = random_bots(seed, 8);
+            let cuboid = Cuboid(Position::new(-4, -3, -5), Position::new(5, 2, 3));
+            for k in 0..=bots.len() {
+                let expected: Vec<_> = cuboid
+                    .points()
+                    .filter(|&p| coverage(&bots, p) == k)
+                    .collect();
+                assert_eq!(expected, cuboid.covered_by_exactly(&bots, k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(36, solve_part2(&parse(EXAMPLE2)));
+    }
+}
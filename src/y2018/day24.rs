@@ -0,0 +1,1099 @@
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// The largest boost the search will consider.
+const MAX_BOOST: u64 = 268_435_456;
+
+/// Two conditions are equal if their groups are, whatever order their damage types were numbered
+/// in or their weaknesses and immunities were written in.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    immune: Army,
+    infection: Army,
+    types: DamageTypes,
+    immune_effective_damages: Vec<Vec<u64>>,
+    infection_effective_damages: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Army {
+    groups: Vec<Group>,
+    alive_groups: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Group {
+    units: u64,
+    health: u64,
+    attack_type: DamageType,
+    attack_damage: u64,
+    initiative: u64,
+    immunities: DamageSet,
+    weaknesses: DamageSet,
+    layout: Layout,
+}
+
+/// How a group's weaknesses and immunities were written, so they can be written back the same way.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct Layout {
+    weak_first: bool,
+    /// Every damage type in the parentheses, in order.
+    order: Vec<DamageType>,
+}
+
+/// The damage types named in an input, numbered in the order they first show up.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DamageTypes(Vec<String>);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DamageType(u8);
+
+/// A set of damage types from the same `DamageTypes`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct DamageSet(u64);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArmyType {
+    Immune,
+    Infection,
+}
+
+/// A group, by its army and its position in the puzzle input (starting from 0).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupId {
+    pub army: ArmyType,
+    pub index: usize,
+}
+
+/// One attacker picking its target, and the damage it expected to deal at the time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub attacker: GroupId,
+    pub defender: GroupId,
+    pub damage: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Attack {
+    pub attacker: GroupId,
+    pub defender: GroupId,
+    pub damage: u64,
+    pub killed: u64,
+}
+
+/// What happened in one round: target selections in the order they were made, then attacks in
+/// initiative order. Attackers that died before their turn don't attack.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Round {
+    pub selections: Vec<Selection>,
+    pub attacks: Vec<Attack>,
+}
+
+/// A change to one group, for trying out variations of a battle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Modifier {
+    /// Adds to (or with a negative amount, takes from) a group's attack damage.
+    Boost(GroupId, i64),
+    AddWeakness(GroupId, String),
+    RemoveWeakness(GroupId, String),
+    AddImmunity(GroupId, String),
+    RemoveImmunity(GroupId, String),
+    Initiative(GroupId, u64),
+}
+
+/// How a battle went: its outcome, how many rounds it took, and the units left in each group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub outcome: Outcome,
+    pub rounds: usize,
+    pub immune: Vec<u64>,
+    pub infection: Vec<u64>,
+}
+
+/// The same battle fought before and after applying some modifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhatIf {
+    pub before: Summary,
+    pub after: Summary,
+}
+
+/// How a battle ended, with the units the winning side has left.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Win {
+        units: u64,
+    },
+    Loss {
+        units: u64,
+    },
+    /// A round went by without anyone dying, so nobody ever will.
+    Stalemate,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Strategy {
+    /// Gallop up to the first winning boost, then bisect below it. Only right if winning is
+    /// monotonic in the boost.
+    Bisect,
+    /// Bisect to find an upper bound, then fight every boost up to it.
+    Exhaustive,
+}
+
+/// Every boost fought during a search, and what came of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoostSearch {
+    pub strategy: Strategy,
+    pub trials: BTreeMap<u64, Outcome>,
+}
+
+#[aoc_generator(day24)]
+pub fn parse(input: &str) -> Box<Condition> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(
+            r"(\d+) units each with (\d+) hit points (?:\(([^)]+)\) )?with an attack that does (\d+) ([a-z]+) damage at initiative (\d+)")
+            .unwrap();
+    }
+    let mut types = DamageTypes::default();
+    let mut armies = input
+        .split("Infection:")
+        .map(|input| {
+            Army::new(
+                RE.captures_iter(input)
+                    .map(|caps| {
+                        let (mut immunities, mut weaknesses) =
+                            (DamageSet::default(), DamageSet::default());
+                        let mut layout = Layout::default();
+                        for list in caps.get(3).iter().flat_map(|s| s.as_str().split("; ")) {
+                            let (set, names) = if let Some(names) = list.strip_prefix("immune to ")
+                            {
+                                (&mut immunities, names)
+                            } else if let Some(names) = list.strip_prefix("weak to ") {
+                                if immunities.is_empty() {
+                                    layout.weak_first = true;
+                                }
+                                (&mut weaknesses, names)
+                            } else {
+                                panic!("unknown modifier {:?}", list)
+                            };
+                            for name in names.split(", ") {
+                                let damage_type = types.intern(name);
+                                set.insert(damage_type);
+                                layout.order.push(damage_type);
+                            }
+                        }
+                        Group {
+                            units: caps[1].parse().unwrap(),
+                            health: caps[2].parse().unwrap(),
+                            attack_type: types.intern(&caps[5]),
+                            attack_damage: caps[4].parse().unwrap(),
+                            initiative: caps[6].parse().unwrap(),
+                            immunities,
+                            weaknesses,
+                            layout,
+                        }
+                    })
+                    .collect(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .into_iter();
+    Box::new(Condition::new(
+        armies.next().unwrap(),
+        armies.next().unwrap(),
+        types,
+    ))
+}
+
+#[aoc(day24, part1)]
+fn solve_part1(condition: &Condition) -> u64 {
+    let mut condition = condition.clone();
+    while condition.immune.alive_groups > 0 && condition.infection.alive_groups > 0 {
+        condition.fight();
+    }
+    condition.immune.total_units() + condition.infection.total_units()
+}
+
+#[aoc(day24, part2)]
+fn solve_part2(condition: &Condition) -> u64 {
+    let search = search_boost(condition, Strategy::Exhaustive);
+    match search.minimum_win() {
+        Some((_, units)) => units,
+        None => panic!("{}", search),
+    }
+}
+
+/// Fights with the immune system's attacks boosted by `boost`.
+pub fn fight_with_boost(condition: &Condition, boost: u64) -> Outcome {
+    let mut immune = condition.immune.clone();
+    immune.boost(boost);
+    Condition::new(immune, condition.infection.clone(), condition.types.clone()).battle()
+}
+
+/// Looks for the smallest boost that lets the immune system win.
+pub fn search_boost(condition: &Condition, strategy: Strategy) -> BoostSearch {
+    let mut trials = BTreeMap::new();
+    let mut fight = |boost| {
+        *trials
+            .entry(boost)
+            .or_insert_with(|| fight_with_boost(condition, boost))
+    };
+    let is_win = |outcome| matches!(outcome, Outcome::Win { .. });
+
+    // Gallop up to a win, then bisect between it and the last boost that didn't win.
+    let (mut lost, mut won) = (None, 0);
+    while !is_win(fight(won)) {
+        if won == MAX_BOOST {
+            return BoostSearch { strategy, trials };
+        }
+        lost = Some(won);
+        won = (won * 2).clamp(1, MAX_BOOST);
+    }
+    if let Some(mut lost) = lost {
+        while won - lost > 1 {
+            let boost = lost + (won - lost) / 2;
+            if is_win(fight(boost)) {
+                won = boost;
+            } else {
+                lost = boost;
+            }
+        }
+    }
+
+    if strategy == Strategy::Exhaustive {
+        let missing: Vec<_> = (0..won).filter(|b| !trials.contains_key(b)).collect();
+        let outcomes: Vec<_> = missing
+            .par_iter()
+            .map(|&boost| (boost, fight_with_boost(condition, boost)))
+            .collect();
+        trials.extend(outcomes);
+    }
+    BoostSearch { strategy, trials }
+}
+
+impl BoostSearch {
+    /// The smallest winning boost fought, and the units the immune system had left.
+    pub fn minimum_win(&self) -> Option<(u64, u64)> {
+        self.trials
+            .iter()
+            .find_map(|(&boost, outcome)| match outcome {
+                Outcome::Win { units } => Some((boost, *units)),
+                _ => None,
+            })
+    }
+
+    /// Runs of boosts that didn't win despite a smaller boost winning, as far as the trials go.
+    pub fn non_monotonic(&self) -> Vec<RangeInclusive<u64>> {
+        let mut regions = vec![];
+        let mut run: Option<RangeInclusive<u64>> = None;
+        let mut won = false;
+        for (&boost, outcome) in &self.trials {
+            match outcome {
+                Outcome::Win { .. } => {
+                    regions.extend(run.take());
+                    won = true;
+                }
+                _ if won => {
+                    let start = run.map_or(boost, |r| *r.start());
+                    run = Some(start..=boost);
+                }
+                _ => {}
+            }
+        }
+        regions.extend(run);
+        regions
+    }
+}
+
+impl Display for BoostSearch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let count = |pred: fn(&Outcome) -> bool| self.trials.values().filter(|o| pred(o)).count();
+        write!(
+            f,
+            "fought {} boosts ({} wins, {} losses, {} stalemates)",
+            self.trials.len(),
+            count(|o| matches!(o, Outcome::Win { .. })),
+            count(|o| matches!(o, Outcome::Loss { .. })),
+            count(|o| *o == Outcome::Stalemate),
+        )?;
+        match self.minimum_win() {
+            Some((boost, units)) => {
+                write!(f, "; boost {} wins with {} units left", boost, units)?;
+                match self.strategy {
+                    Strategy::Exhaustive => write!(f, ", and every smaller boost was fought")?,
+                    Strategy::Bisect => {
+                        write!(f, ", assuming no smaller boost wins that bisection skipped")?
+                    }
+                }
+            }
+            None => write!(f, "; no boost up to {} wins", MAX_BOOST)?,
+        }
+        for region in self.non_monotonic() {
+            write!(
+                f,
+                "; boosts {}..={} don't win although a smaller one does",
+                region.start(),
+                region.end()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Condition {
+    fn eq(&self, other: &Condition) -> bool {
+        let groups = |c: &Condition| {
+            let names = |set: DamageSet| {
+                let mut names = c.types.names(set);
+                names.sort_unstable();
+                names.into_iter().map(str::to_owned).collect::<Vec<_>>()
+            };
+            let groups = |army: &Army| {
+                army.groups
+                    .iter()
+                    .map(|g| {
+                        (
+                            (g.units, g.health, g.attack_damage, g.initiative),
+                            c.types.name(g.attack_type).to_owned(),
+                            names(g.immunities),
+                            names(g.weaknesses),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            };
+            (
+                groups(&c.immune),
+                groups(&c.infection),
+                c.immune.alive_groups,
+                c.infection.alive_groups,
+            )
+        };
+        groups(self) == groups(other)
+    }
+}
+
+impl Eq for Condition {}
+
+impl Condition {
+    fn new(immune: Army, infection: Army, types: DamageTypes) -> Condition {
+        Condition {
+            immune_effective_damages: immune
+                .groups
+                .iter()
+                .map(|a| {
+                    infection
+                        .groups
+                        .iter()
+                        .map(|b| b.effective_damage_taken(a))
+                        .collect()
+                })
+                .collect(),
+            infection_effective_damages: infection
+                .groups
+                .iter()
+                .map(|a| {
+                    immune
+                        .groups
+                        .iter()
+                        .map(|b| b.effective_damage_taken(a))
+                        .collect()
+                })
+                .collect(),
+            immune,
+            infection,
+            types,
+        }
+    }
+
+    /// Writes the condition out in the puzzle's format, so that parsing it gives it back.
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+        for (title, army) in &[
+            ("Immune System", &self.immune),
+            ("Infection", &self.infection),
+        ] {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(title);
+            out.push_str(":\n");
+            for g in &army.groups {
+                // Types keep the order they were written in, and any added since go after them.
+                let names = |set: DamageSet| {
+                    let mut types: Vec<_> = set.iter().collect();
+                    types.sort_by_key(|t| {
+                        let written = g.layout.order.iter().position(|o| o == t);
+                        (written.unwrap_or(usize::MAX), t.0)
+                    });
+                    let names: Vec<_> = types.into_iter().map(|t| self.types.name(t)).collect();
+                    names.join(", ")
+                };
+                let mut modifiers = vec![];
+                if !g.immunities.is_empty() {
+                    modifiers.push(format!("immune to {}", names(g.immunities)));
+                }
+                if !g.weaknesses.is_empty() {
+                    modifiers.push(format!("weak to {}", names(g.weaknesses)));
+                }
+                if g.layout.weak_first {
+                    modifiers.reverse();
+                }
+                let modifiers = if modifiers.is_empty() {
+                    String::new()
+                } else {
+                    format!("({}) ", modifiers.join("; "))
+                };
+                out.push_str(&format!(
+                    "{} units each with {} hit points {}with an attack that does {} {} damage at initiative {}\n",
+                    g.units,
+                    g.health,
+                    modifiers,
+                    g.attack_damage,
+                    self.types.name(g.attack_type),
+                    g.initiative
+                ));
+            }
+        }
+        out
+    }
+
+    /// Fights until one side is wiped out, or until nobody can kill anyone.
+    fn battle(mut self) -> Outcome {
+        self.battle_with(|_| {})
+    }
+
+    fn battle_with(&mut self, mut on_round: impl FnMut(Round)) -> Outcome {
+        while self.immune.alive_groups > 0 && self.infection.alive_groups > 0 {
+            let round = self.fight_round();
+            let units_killed: u64 = round.attacks.iter().map(|a| a.killed).sum();
+            on_round(round);
+            if units_killed == 0 {
+                return Outcome::Stalemate;
+            }
+        }
+        if self.infection.alive_groups == 0 {
+            Outcome::Win {
+                units: self.immune.total_units(),
+            }
+        } else {
+            Outcome::Loss {
+                units: self.infection.total_units(),
+            }
+        }
+    }
+
+    /// Fights the battle to the end, recording every round.
+    pub fn transcript(&self) -> (Vec<Round>, Summary) {
+        let mut condition = self.clone();
+        let mut rounds = vec![];
+        let outcome = condition.battle_with(|round| rounds.push(round));
+        let summary = Summary {
+            outcome,
+            rounds: rounds.len(),
+            immune: condition.immune.groups.iter().map(|g| g.units).collect(),
+            infection: condition.infection.groups.iter().map(|g| g.units).collect(),
+        };
+        (rounds, summary)
+    }
+
+    /// Returns a copy of the starting condition with `modifiers` applied in order.
+    pub fn modified(&self, modifiers: &[Modifier]) -> Condition {
+        let (mut immune, mut infection) = (self.immune.clone(), self.infection.clone());
+        let mut types = self.types.clone();
+        for modifier in modifiers {
+            let id = match modifier {
+                Modifier::Boost(id, _)
+                | Modifier::AddWeakness(id, _)
+                | Modifier::RemoveWeakness(id, _)
+                | Modifier::AddImmunity(id, _)
+                | Modifier::RemoveImmunity(id, _)
+                | Modifier::Initiative(id, _) => id,
+            };
+            let group = match id.army {
+                ArmyType::Immune => &mut immune.groups[id.index],
+                ArmyType::Infection => &mut infection.groups[id.index],
+            };
+            match modifier {
+                Modifier::Boost(_, amount) => {
+                    group.attack_damage = (group.attack_damage as i64 + amount).max(0) as u64
+                }
+                Modifier::AddWeakness(_, kind) => group.weaknesses.insert(types.intern(kind)),
+                Modifier::RemoveWeakness(_, kind) => group.weaknesses.remove(types.intern(kind)),
+                Modifier::AddImmunity(_, kind) => group.immunities.insert(types.intern(kind)),
+                Modifier::RemoveImmunity(_, kind) => group.immunities.remove(types.intern(kind)),
+                Modifier::Initiative(_, initiative) => group.initiative = *initiative,
+            }
+        }
+        Condition::new(immune, infection, types)
+    }
+
+    /// Fights the battle as it is and with `modifiers` applied, to compare the two.
+    pub fn what_if(&self, modifiers: &[Modifier]) -> WhatIf {
+        WhatIf {
+            before: self.transcript().1,
+            after: self.modified(modifiers).transcript().1,
+        }
+    }
+
+    /// Fights a single round, returning units killed.
+    fn fight(&mut self) -> u64 {
+        self.fight_round().attacks.iter().map(|a| a.killed).sum()
+    }
+
+    fn fight_round(&mut self) -> Round {
+        let mut round = Round::default();
+        // Select targets
+        let mut target_selection: BinaryHeap<(u64, ArmyType, usize, usize)> = BinaryHeap::new();
+        let mut immune_attacked = HashSet::new();
+        let mut infection_attacked = HashSet::new();
+        for att_index in self.immune.indexes_by_power() {
+            let defender = self.immune_effective_damages[att_index]
+                .iter()
+                .enumerate()
+                .filter(|(i, d)| {
+                    **d > 0
+                        && self.infection.groups[*i].units > 0
+                        && !infection_attacked.contains(i)
+                })
+                .max_by_key(|(i, d)| {
+                    (
+                        *d,
+                        self.infection.groups[*i].effective_power(),
+                        self.infection.groups[*i].initiative,
+                    )
+                });
+
+            if let Some((def_index, &damage)) = defender {
+                round.selections.push(Selection {
+                    attacker: GroupId::new(ArmyType::Immune, att_index),
+                    defender: GroupId::new(ArmyType::Infection, def_index),
+                    damage: damage * self.immune.groups[att_index].units,
+                });
+                infection_attacked.insert(def_index);
+                target_selection.push((
+                    self.immune.groups[att_index].initiative,
+                    ArmyType::Immune,
+                    att_index,
+                    def_index,
+                ));
+            }
+        }
+        for att_index in self.infection.indexes_by_power() {
+            let defender = self.infection_effective_damages[att_index]
+                .iter()
+                .enumerate()
+                .filter(|(i, d)| {
+                    **d > 0 && self.immune.groups[*i].units > 0 && !immune_attacked.contains(i)
+                })
+                .max_by_key(|(i, d)| {
+                    (
+                        *d,
+                        self.immune.groups[*i].effective_power(),
+                        self.immune.groups[*i].initiative,
+                    )
+                });
+
+            if let Some((def_index, &damage)) = defender {
+                round.selections.push(Selection {
+                    attacker: GroupId::new(ArmyType::Infection, att_index),
+                    defender: GroupId::new(ArmyType::Immune, def_index),
+                    damage: damage * self.infection.groups[att_index].units,
+                });
+                immune_attacked.insert(def_index);
+                target_selection.push((
+                    self.infection.groups[att_index].initiative,
+                    ArmyType::Infection,
+                    att_index,
+                    def_index,
+                ));
+            }
+        }
+
+        // Attack
+        while let Some((_, army, att_index, def_index)) = target_selection.pop() {
+            if let (Some(attacker), Some(defender), damage) = match army {
+                ArmyType::Immune => (
+                    self.immune.groups.get(att_index),
+                    self.infection.groups.get_mut(def_index),
+                    self.immune_effective_damages[att_index][def_index],
+                ),
+                ArmyType::Infection => (
+                    self.infection.groups.get(att_index),
+                    self.immune.groups.get_mut(def_index),
+                    self.infection_effective_damages[att_index][def_index],
+                ),
+            } {
+                if attacker.units == 0 || defender.units == 0 {
+                    continue;
+                }
+                let damage = damage * attacker.units;
+                round.attacks.push(Attack {
+                    attacker: GroupId::new(army, att_index),
+                    defender: GroupId::new(army.enemy(), def_index),
+                    damage,
+                    killed: defender.take_damage(damage),
+                });
+                if defender.units == 0 {
+                    match army {
+                        ArmyType::Immune => self.infection.alive_groups -= 1,
+                        ArmyType::Infection => self.immune.alive_groups -= 1,
+                    }
+                }
+            }
+        }
+        round
+    }
+}
+
+impl DamageTypes {
+    /// Looks up a damage type by name, numbering it if it hasn't been seen before.
+    pub fn intern(&mut self, name: &str) -> DamageType {
+        let index = match self.0.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                assert!(self.0.len() < 64, "too many damage types");
+                self.0.push(name.to_owned());
+                self.0.len() - 1
+            }
+        };
+        DamageType(index as u8)
+    }
+
+    pub fn name(&self, damage_type: DamageType) -> &str {
+        &self.0[damage_type.0 as usize]
+    }
+
+    /// Names the types in `set`, in the order they were first seen.
+    pub fn names(&self, set: DamageSet) -> Vec<&str> {
+        set.iter().map(|t| self.name(t)).collect()
+    }
+}
+
+impl DamageSet {
+    pub fn contains(self, damage_type: DamageType) -> bool {
+        self.0 & (1 << damage_type.0) != 0
+    }
+
+    pub fn insert(&mut self, damage_type: DamageType) {
+        self.0 |= 1 << damage_type.0;
+    }
+
+    pub fn remove(&mut self, damage_type: DamageType) {
+        self.0 &= !(1 << damage_type.0);
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = DamageType> {
+        (0..64)
+            .filter(move |i| self.0 & (1 << i) != 0)
+            .map(DamageType)
+    }
+}
+
+impl ArmyType {
+    fn enemy(self) -> ArmyType {
+        match self {
+            ArmyType::Immune => ArmyType::Infection,
+            ArmyType::Infection => ArmyType::Immune,
+        }
+    }
+}
+
+impl GroupId {
+    pub fn new(army: ArmyType, index: usize) -> GroupId {
+        GroupId { army, index }
+    }
+}
+
+impl Army {
+    fn new(groups: Vec<Group>) -> Army {
+        Army {
+            alive_groups: groups.len(),
+            groups,
+        }
+    }
+
+    fn boost(&mut self, amount: u64) {
+        for group in &mut self.groups {
+            group.attack_damage += amount;
+        }
+    }
+
+    fn total_units(&self) -> u64 {
+        self.groups.iter().map(|g| g.units).sum()
+    }
+
+    fn indexes_by_power(&self) -> impl Iterator<Item = usize> {
+        let mut groups = self
+            .groups
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| g.units > 0)
+            .map(|(i, g)| (g.effective_power(), g.initiative, i))
+            .collect::<Vec<_>>();
+        groups.sort();
+        groups.into_iter().map(|(_, _, i)| i).rev()
+    }
+}
+
+impl Group {
+    fn effective_power(&self) -> u64 {
+        self.units * self.attack_damage
+    }
+
+    fn effective_damage_taken(&self, other: &Group) -> u64 {
+        if self.immunities.contains(other.attack_type) {
+            return 0;
+        }
+        if self.weaknesses.contains(other.attack_type) {
+            return other.attack_damage * 2;
+        }
+        other.attack_damage
+    }
+
+    fn take_damage(&mut self, damage: u64) -> u64 {
+        let units_killed = self.units.min(damage / self.health);
+        self.units -= units_killed;
+        units_killed
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Immune system:")?;
+        self.immune
+            .groups
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| g.units > 0)
+            .try_for_each(|(i, g)| writeln!(f, "Group {} contains {} units", i + 1, g.units))?;
+        writeln!(f, "Infection:")?;
+        self.infection
+            .groups
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| g.units > 0)
+            .try_for_each(|(i, g)| writeln!(f, "Group {} contains {} units", i + 1, g.units))?;
+        Ok(())
+    }
+}
+
+impl Display for ArmyType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ArmyType::Immune => write!(f, "Immune System"),
+            ArmyType::Infection => write!(f, "Infection"),
+        }
+    }
+}
+
+/// Writes the round the way the puzzle describes it.
+impl Display for Round {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for s in &self.selections {
+            writeln!(
+                f,
+                "{} group {} would deal defending group {} {} damage",
+                s.attacker.army,
+                s.attacker.index + 1,
+                s.defender.index + 1,
+                s.damage
+            )?;
+        }
+        writeln!(f)?;
+        for a in &self.attacks {
+            writeln!(
+                f,
+                "{} group {} attacks defending group {}, killing {} units",
+                a.attacker.army,
+                a.attacker.index + 1,
+                a.defender.index + 1,
+                a.killed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists what changed between the two battles.
+impl Display for WhatIf {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (before, after) = (&self.before, &self.after);
+        if before.outcome != after.outcome {
+            writeln!(f, "outcome: {:?} -> {:?}", before.outcome, after.outcome)?;
+        }
+        if before.rounds != after.rounds {
+            writeln!(f, "rounds: {} -> {}", before.rounds, after.rounds)?;
+        }
+        let armies = [
+            (ArmyType::Immune, &before.immune, &after.immune),
+            (ArmyType::Infection, &before.infection, &after.infection),
+        ];
+        for (army, before, after) in armies.iter() {
+            for (i, (b, a)) in before.iter().zip(after.iter()).enumerate() {
+                if b != a {
+                    writeln!(f, "{} group {}: {} -> {} units", army, i + 1, b, a)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "
+Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4";
+
+    #[test]
+    fn test_parse() {
+        let condition = parse(EXAMPLE);
+        assert_eq!(condition.immune.groups.len(), 2);
+        assert_eq!(condition.infection.groups.len(), 2);
+        let types = &condition.types;
+        let group = &condition.immune.groups[0];
+        assert_eq!(
+            (17, 5390, 4507, 2),
+            (
+                group.units,
+                group.health,
+                group.attack_damage,
+                group.initiative
+            )
+        );
+        assert_eq!("fire", types.name(group.attack_type));
+        assert!(group.immunities.is_empty());
+        assert_eq!(
+            vec!["radiation", "bludgeoning"],
+            types.names(group.weaknesses)
+        );
+        assert_eq!(
+            vec!["radiation"],
+            types.names(condition.infection.groups[1].immunities)
+        );
+        assert_eq!(
+            vec!["fire", "cold"],
+            types.names(condition.infection.groups[1].weaknesses)
+        );
+        assert_eq!(
+            vec!["radiation", "bludgeoning", "fire", "slashing", "cold"],
+            types.0
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let condition = parse(EXAMPLE);
+        assert_eq!(EXAMPLE.trim_start(), condition.serialize().trim_end());
+        assert_eq!(condition, parse(&condition.serialize()));
+
+        let mut types = DamageTypes::default();
+        // Numbered the other way round from how parsing the output numbers them.
+        let (fire, cold) = (types.intern("fire"), types.intern("cold"));
+        let groups = |attack_type| {
+            Army::new(vec![Group {
+                units: 3,
+                health: 10,
+                attack_type,
+                attack_damage: 4,
+                initiative: 1,
+                immunities: DamageSet::default(),
+                weaknesses: DamageSet::default(),
+                layout: Layout::default(),
+            }])
+        };
+        let generated = Condition::new(groups(cold), groups(fire), types);
+        assert_eq!(
+            "Immune System:\n\
+             3 units each with 10 hit points with an attack that does 4 cold damage at initiative 1\n\
+             \n\
+             Infection:\n\
+             3 units each with 10 hit points with an attack that does 4 fire damage at initiative 1\n",
+            generated.serialize()
+        );
+        assert_eq!(generated, *parse(&generated.serialize()));
+
+        // Weaknesses written first, and types not in the order they were first seen.
+        let input = "Immune System:
+17 units each with 5390 hit points (weak to radiation; immune to fire) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to slashing, fire; weak to cold) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to slashing, radiation) with an attack that does 116 bludgeoning damage at initiative 1
+";
+        let condition = parse(input);
+        assert_eq!(input, condition.serialize());
+        assert_eq!(condition, parse(&condition.serialize()));
+
+        // Modifiers keep the written order and put new types last.
+        let id = GroupId {
+            army: ArmyType::Immune,
+            index: 0,
+        };
+        let modified = condition.modified(&[
+            Modifier::AddWeakness(id, "cold".to_owned()),
+            Modifier::RemoveImmunity(id, "fire".to_owned()),
+        ]);
+        assert!(modified.serialize().starts_with(
+            "Immune System:\n17 units each with 5390 hit points (weak to radiation, cold) with"
+        ));
+        assert_eq!(modified, *parse(&modified.serialize()));
+        assert_ne!(*condition, modified);
+    }
+
+    #[test]
+    fn test_transcript() {
+        let (rounds, summary) = parse(EXAMPLE).transcript();
+        assert_eq!(
+            "\
+Immune System group 1 would deal defending group 2 153238 damage
+Immune System group 2 would deal defending group 1 24725 damage
+Infection group 1 would deal defending group 1 185832 damage
+Infection group 2 would deal defending group 2 107640 damage
+
+Infection group 2 attacks defending group 2, killing 84 units
+Immune System group 2 attacks defending group 1, killing 4 units
+Immune System group 1 attacks defending group 2, killing 51 units
+Infection group 1 attacks defending group 1, killing 17 units
+",
+            rounds[0].to_string()
+        );
+        assert_eq!(
+            Summary {
+                outcome: Outcome::Loss { units: 5216 },
+                rounds: 8,
+                immune: vec![0, 0],
+                infection: vec![782, 4434],
+            },
+            summary
+        );
+    }
+
+    #[test]
+    fn test_what_if() {
+        let condition = parse(EXAMPLE);
+        let boost = [
+            Modifier::Boost(GroupId::new(ArmyType::Immune, 0), 1570),
+            Modifier::Boost(GroupId::new(ArmyType::Immune, 1), 1570),
+        ];
+        let what_if = condition.what_if(&boost);
+        assert_eq!(Outcome::Win { units: 51 }, what_if.after.outcome);
+        assert_eq!(what_if.after, fight_summary(&condition, 1570));
+        assert!(what_if
+            .to_string()
+            .starts_with("outcome: Loss { units: 5216 } -> Win { units: 51 }\n"));
+
+        let tweaks = [
+            Modifier::Boost(GroupId::new(ArmyType::Infection, 0), -16),
+            Modifier::AddImmunity(GroupId::new(ArmyType::Infection, 1), "fire".to_owned()),
+            Modifier::RemoveWeakness(GroupId::new(ArmyType::Infection, 1), "fire".to_owned()),
+            Modifier::AddWeakness(GroupId::new(ArmyType::Immune, 0), "fire".to_owned()),
+            Modifier::RemoveImmunity(GroupId::new(ArmyType::Immune, 1), "fire".to_owned()),
+            Modifier::Initiative(GroupId::new(ArmyType::Immune, 0), 5),
+        ];
+        let modified = condition.modified(&tweaks);
+        let (infection, immune) = (&modified.infection.groups, &modified.immune.groups);
+        assert_eq!(100, infection[0].attack_damage);
+        let types = &modified.types;
+        assert_eq!(
+            vec!["radiation", "fire"],
+            types.names(infection[1].immunities)
+        );
+        assert_eq!(vec!["cold"], types.names(infection[1].weaknesses));
+        assert_eq!(
+            vec!["radiation", "bludgeoning", "fire"],
+            types.names(immune[0].weaknesses)
+        );
+        assert!(immune[1].immunities.is_empty());
+        assert_eq!(5, immune[0].initiative);
+        assert_eq!(0, modified.immune_effective_damages[0][1]);
+    }
+
+    fn fight_summary(condition: &Condition, boost: u64) -> Summary {
+        let mut immune = condition.immune.clone();
+        immune.boost(boost);
+        Condition::new(immune, condition.infection.clone(), condition.types.clone())
+            .transcript()
+            .1
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(5216, solve_part1(&parse(EXAMPLE)));
+    }
+
+    #[test]
+    fn test_fight_with_boost() {
+        let condition = parse(EXAMPLE);
+        assert_eq!(
+            Outcome::Loss { units: 5216 },
+            fight_with_boost(&condition, 0)
+        );
+        assert_eq!(
+            Outcome::Win { units: 51 },
+            fight_with_boost(&condition, 1570)
+        );
+    }
+
+    #[test]
+    fn test_search_boost() {
+        let condition = parse(EXAMPLE);
+        let bisect = search_boost(&condition, Strategy::Bisect);
+        assert_eq!(Some((1570, 51)), bisect.minimum_win());
+        assert!(bisect.trials.len() < 30);
+        let exhaustive = search_boost(&condition, Strategy::Exhaustive);
+        assert_eq!(Some((1570, 51)), exhaustive.minimum_win());
+        assert_eq!(1570, exhaustive.trials.range(..1570).count());
+        assert!(exhaustive.non_monotonic().is_empty());
+        assert!(exhaustive
+            .to_string()
+            .contains("boost 1570 wins with 51 units left, and every smaller boost was fought"));
+    }
+
+    #[test]
+    fn test_non_monotonic() {
+        let win = Outcome::Win { units: 1 };
+        let loss = Outcome::Loss { units: 1 };
+        let search = BoostSearch {
+            strategy: Strategy::Exhaustive,
+            trials: vec![
+                (0, loss),
+                (1, Outcome::Stalemate),
+                (2, win),
+                (3, loss),
+                (4, Outcome::Stalemate),
+                (5, win),
+                (6, loss),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(Some((2, 1)), search.minimum_win());
+        assert_eq!(vec![3..=4, 6..=6], search.non_monotonic());
+        assert_eq!(
+            "fought 7 boosts (2 wins, 3 losses, 2 stalemates); boost 2 wins with 1 units left, \
+             and every smaller boost was fought; boosts 3..=4 don't win although a smaller one \
+             does; boosts 6..=6 don't win although a smaller one does",
+            search.to_string()
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(51, solve_part2(&parse(EXAMPLE)));
+    }
+}
@@ -1,11 +1,12 @@
+use crate::cluster::{cluster, manhattan, Clusters};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point4(i32, i32, i32, i32);
+pub struct Point4(pub i32, pub i32, pub i32, pub i32);
 
 #[aoc_generator(day25)]
-fn parse(input: &str) -> Vec<Point4> {
+pub fn parse(input: &str) -> Vec<Point4> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"(-?\d+),(-?\d+),(-?\d+),(-?\d+)").unwrap();
     }
@@ -24,44 +25,19 @@ fn parse(input: &str) -> Vec<Point4> {
 }
 
 #[aoc(day25, part1)]
-fn solve_part1(points: &[Point4]) -> u32 {
-    let mut adjacency = vec![vec![]; points.len()];
-    for i in 0..points.len() {
-        for j in i + 1..points.len() {
-            if points[i].distance(points[j]) <= 3 {
-                adjacency[i].push(j);
-                adjacency[j].push(i);
-            }
-        }
-    }
+fn solve_part1(points: &[Point4]) -> usize {
+    constellations(points).len()
+}
 
-    let mut constellations = 0;
-    let mut visited = vec![false; points.len()];
-    for i in 0..points.len() {
-        if visited[i] {
-            continue;
-        }
-        visited[i] = true;
-        constellations += 1;
-        let mut queue = vec![i];
-        while let Some(index) = queue.pop() {
-            for &j in &adjacency[index] {
-                if !visited[j] {
-                    queue.push(j);
-                    visited[j] = true;
-                }
-            }
-        }
-    }
-    constellations
+/// Points up to 3 apart are in the same constellation, as are points joined through others.
+pub fn constellations(points: &[Point4]) -> Clusters<4> {
+    let coordinates: Vec<_> = points.iter().map(|p| p.coordinates()).collect();
+    cluster(&coordinates, 3, manhattan)
 }
 
 impl Point4 {
-    fn distance(self, other: Point4) -> i32 {
-        (self.0 - other.0).abs()
-            + (self.1 - other.1).abs()
-            + (self.2 - other.2).abs()
-            + (self.3 - other.3).abs()
+    fn coordinates(self) -> [i64; 4] {
+        [self.0.into(), self.1.into(), self.2.into(), self.3.into()]
     }
 }
 
@@ -83,6 +59,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_constellations() {
+        let points = parse("0,0,0,0\n3,0,0,0\n0,0,0,6\n9,0,0,0\n0,3,0,0\n0,0,0,3\n12,0,0,0");
+        let constellations = constellations(&points);
+        assert_eq!(vec![0, 0, 0, 1, 0, 0, 1], constellations.labels);
+        assert_eq!(vec![5, 2], constellations.sizes);
+        assert_eq!(([9, 0, 0, 0], [12, 0, 0, 0]), constellations.bounds[1]);
+    }
+
     #[test]
     fn test_part1() {
         assert_eq!(
@@ -0,0 +1,88 @@
+use crate::registry::Solution;
+use crate::*;
+
+mod day1;
+pub mod day10;
+mod day11;
+mod day12;
+pub mod day13;
+mod day14;
+pub mod day15;
+mod day16;
+pub mod day17;
+mod day18;
+mod day19;
+mod day2;
+pub mod day20;
+mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+/// Every 2018 solution, ordered by day, part and name.
+pub fn solutions() -> &'static [Solution] {
+    crate::registry::solutions![
+        (1, 1, None, Factory::day1_part1),
+        (1, 2, None, Factory::day1_part2),
+        (2, 1, None, Factory::day2_part1),
+        (2, 2, None, Factory::day2_part2),
+        (3, 1, None, Factory::day3_part1),
+        (3, 2, None, Factory::day3_part2),
+        (4, 1, None, Factory::day4_part1),
+        (4, 2, None, Factory::day4_part2),
+        (5, 1, None, Factory::day5_part1),
+        (5, 2, None, Factory::day5_part2),
+        (6, 1, None, Factory::day6_part1),
+        (6, 2, None, Factory::day6_part2),
+        (7, 1, None, Factory::day7_part1),
+        (7, 2, None, Factory::day7_part2),
+        (8, 1, None, Factory::day8_part1),
+        (8, 1, Some("tree"), Factory::day8_part1_tree),
+        (8, 2, None, Factory::day8_part2),
+        (8, 2, Some("tree"), Factory::day8_part2_tree),
+        (9, 1, None, Factory::day9_part1),
+        (9, 1, Some("vecdeque"), Factory::day9_part1_vecdeque),
+        (9, 2, None, Factory::day9_part2),
+        (9, 2, Some("vecdeque"), Factory::day9_part2_vecdeque),
+        (10, 1, None, Factory::day10_part1),
+        (10, 2, None, Factory::day10_part2),
+        (11, 1, None, Factory::day11_part1),
+        (11, 2, None, Factory::day11_part2),
+        (12, 1, None, Factory::day12_part1),
+        (12, 2, None, Factory::day12_part2),
+        (13, 1, None, Factory::day13_part1),
+        (13, 2, None, Factory::day13_part2),
+        (14, 1, None, Factory::day14_part1),
+        (14, 2, None, Factory::day14_part2),
+        (15, 1, None, Factory::day15_part1),
+        (15, 1, Some("verified"), Factory::day15_part1_verified),
+        (15, 2, None, Factory::day15_part2),
+        (16, 1, None, Factory::day16_part1),
+        (16, 2, None, Factory::day16_part2),
+        (17, 1, None, Factory::day17_part1),
+        (17, 2, None, Factory::day17_part2),
+        (18, 1, None, Factory::day18_part1),
+        (18, 2, None, Factory::day18_part2),
+        (19, 1, None, Factory::day19_part1),
+        (19, 2, None, Factory::day19_part2),
+        (20, 1, None, Factory::day20_part1),
+        (20, 2, None, Factory::day20_part2),
+        (21, 1, None, Factory::day21_part1),
+        (21, 2, None, Factory::day21_part2),
+        (22, 1, None, Factory::day22_part1),
+        (22, 2, None, Factory::day22_part2),
+        (23, 1, None, Factory::day23_part1),
+        (23, 2, None, Factory::day23_part2),
+        (24, 1, None, Factory::day24_part1),
+        (24, 2, None, Factory::day24_part2),
+        (25, 1, None, Factory::day25_part1),
+    ]
+}